@@ -28,6 +28,7 @@ fn run() -> Result<(), error::CliError> {
         Some(Step::Tag(config)) => config.run(),
         Some(Step::Push(config)) => config.run(),
         Some(Step::Config(config)) => config.run(),
+        Some(Step::Apply(config)) => config.run(),
         None => release_matches.release.run(),
     }
 }
@@ -86,6 +87,7 @@ pub enum Step {
     Tag(steps::tag::TagStep),
     Push(steps::push::PushStep),
     Config(steps::config::ConfigStep),
+    Apply(steps::apply::ApplyStep),
 }
 
 #[derive(clap::Args, Debug, Clone)]
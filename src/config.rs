@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::CargoResult;
 use crate::ops::cargo;
+use crate::steps::changes::CommitStatus;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, default)]
@@ -28,7 +29,19 @@ pub struct Config {
     pub consolidate_commits: Option<bool>,
     pub pre_release_commit_message: Option<String>,
     pub pre_release_replacements: Option<Vec<Replace>>,
+    pub version_files: Option<Vec<VersionFile>>,
     pub pre_release_hook: Option<Command>,
+    pub changelog_update: Option<bool>,
+    pub changelog_path: Option<PathBuf>,
+    pub commit_types: Option<std::collections::BTreeMap<String, CommitStatus>>,
+    pub public_dependencies: Option<Vec<String>>,
+    pub manifest_breaking: Option<ManifestBreaking>,
+    pub commit_walk: Option<CommitWalk>,
+    pub changes_include: Option<Vec<String>>,
+    pub changes_exclude: Option<Vec<String>>,
+    pub changes_ignore_authors: Option<Vec<String>>,
+    pub changes_ignore_messages: Option<Vec<String>>,
+    pub issue_url: Option<String>,
     pub tag_message: Option<String>,
     pub tag_prefix: Option<String>,
     pub tag_name: Option<String>,
@@ -36,7 +49,12 @@ pub struct Config {
     pub enable_features: Option<Vec<String>>,
     pub enable_all_features: Option<bool>,
     pub dependent_version: Option<DependentVersion>,
+    pub cascade_bumps: Option<bool>,
     pub metadata: Option<MetadataPolicy>,
+    pub metadata_template: Option<String>,
+    pub version_scheme: Option<VersionScheme>,
+    pub version_source: Option<VersionSource>,
+    pub prerelease_channels: Option<Vec<String>>,
     pub target: Option<String>,
     pub rate_limit: RateLimit,
     pub certs_source: Option<CertsSource>,
@@ -79,7 +97,24 @@ impl Config {
             consolidate_commits: Some(empty.consolidate_commits()),
             pre_release_commit_message: Some(empty.pre_release_commit_message().to_owned()),
             pre_release_replacements: Some(empty.pre_release_replacements().to_vec()),
+            version_files: Some(empty.version_files().to_vec()),
             pre_release_hook: empty.pre_release_hook().cloned(),
+            changelog_update: Some(empty.changelog_update()),
+            changelog_path: Some(empty.changelog_path().to_owned()),
+            commit_types: Some(
+                DEFAULT_COMMIT_TYPES
+                    .iter()
+                    .map(|(t, s)| ((*t).to_owned(), *s))
+                    .collect(),
+            ),
+            public_dependencies: Some(empty.public_dependencies().to_vec()),
+            manifest_breaking: Some(empty.manifest_breaking()),
+            commit_walk: Some(empty.commit_walk()),
+            changes_include: Some(empty.changes_include().to_vec()),
+            changes_exclude: Some(empty.changes_exclude().to_vec()),
+            changes_ignore_authors: Some(empty.changes_ignore_authors().to_vec()),
+            changes_ignore_messages: Some(empty.changes_ignore_messages().to_vec()),
+            issue_url: empty.issue_url().map(|s| s.to_owned()),
             tag_message: Some(empty.tag_message().to_owned()),
             tag_prefix: None, // Skipping, its location dependent
             tag_name: Some(empty.tag_name().to_owned()),
@@ -87,7 +122,17 @@ impl Config {
             enable_features: Some(empty.enable_features().to_vec()),
             enable_all_features: Some(empty.enable_all_features()),
             dependent_version: Some(empty.dependent_version()),
+            cascade_bumps: Some(empty.cascade_bumps()),
             metadata: Some(empty.metadata()),
+            metadata_template: Some(empty.metadata_template().to_owned()),
+            version_scheme: Some(empty.version_scheme()),
+            version_source: Some(empty.version_source()),
+            prerelease_channels: Some(
+                empty
+                    .prerelease_channels()
+                    .map(|s| s.to_owned())
+                    .collect::<Vec<String>>(),
+            ),
             target: None,
             rate_limit: RateLimit::from_defaults(),
             certs_source: Some(empty.certs_source()),
@@ -141,9 +186,47 @@ impl Config {
         if let Some(pre_release_replacements) = source.pre_release_replacements.as_deref() {
             self.pre_release_replacements = Some(pre_release_replacements.to_owned());
         }
+        if let Some(version_files) = source.version_files.as_deref() {
+            self.version_files = Some(version_files.to_owned());
+        }
         if let Some(pre_release_hook) = source.pre_release_hook.as_ref() {
             self.pre_release_hook = Some(pre_release_hook.to_owned());
         }
+        if let Some(changelog_update) = source.changelog_update {
+            self.changelog_update = Some(changelog_update);
+        }
+        if let Some(changelog_path) = source.changelog_path.as_deref() {
+            self.changelog_path = Some(changelog_path.to_owned());
+        }
+        if let Some(commit_types) = source.commit_types.as_ref() {
+            self.commit_types
+                .get_or_insert_with(Default::default)
+                .extend(commit_types.iter().map(|(k, v)| (k.clone(), *v)));
+        }
+        if let Some(public_dependencies) = source.public_dependencies.as_deref() {
+            self.public_dependencies = Some(public_dependencies.to_owned());
+        }
+        if let Some(manifest_breaking) = source.manifest_breaking {
+            self.manifest_breaking = Some(manifest_breaking);
+        }
+        if let Some(commit_walk) = source.commit_walk {
+            self.commit_walk = Some(commit_walk);
+        }
+        if let Some(changes_include) = source.changes_include.as_deref() {
+            self.changes_include = Some(changes_include.to_owned());
+        }
+        if let Some(changes_exclude) = source.changes_exclude.as_deref() {
+            self.changes_exclude = Some(changes_exclude.to_owned());
+        }
+        if let Some(changes_ignore_authors) = source.changes_ignore_authors.as_deref() {
+            self.changes_ignore_authors = Some(changes_ignore_authors.to_owned());
+        }
+        if let Some(changes_ignore_messages) = source.changes_ignore_messages.as_deref() {
+            self.changes_ignore_messages = Some(changes_ignore_messages.to_owned());
+        }
+        if let Some(issue_url) = source.issue_url.as_deref() {
+            self.issue_url = Some(issue_url.to_owned());
+        }
         if let Some(tag_message) = source.tag_message.as_deref() {
             self.tag_message = Some(tag_message.to_owned());
         }
@@ -165,9 +248,24 @@ impl Config {
         if let Some(dependent_version) = source.dependent_version {
             self.dependent_version = Some(dependent_version);
         }
+        if let Some(cascade_bumps) = source.cascade_bumps {
+            self.cascade_bumps = Some(cascade_bumps);
+        }
         if let Some(metadata) = source.metadata {
             self.metadata = Some(metadata);
         }
+        if let Some(metadata_template) = source.metadata_template.as_deref() {
+            self.metadata_template = Some(metadata_template.to_owned());
+        }
+        if let Some(version_scheme) = source.version_scheme {
+            self.version_scheme = Some(version_scheme);
+        }
+        if let Some(version_source) = source.version_source {
+            self.version_source = Some(version_source);
+        }
+        if let Some(prerelease_channels) = source.prerelease_channels.as_deref() {
+            self.prerelease_channels = Some(prerelease_channels.to_owned());
+        }
         if let Some(target) = source.target.as_deref() {
             self.target = Some(target.to_owned());
         }
@@ -258,10 +356,96 @@ impl Config {
             .unwrap_or(&[])
     }
 
+    pub fn version_files(&self) -> &[VersionFile] {
+        self.version_files
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
     pub fn pre_release_hook(&self) -> Option<&Command> {
         self.pre_release_hook.as_ref()
     }
 
+    pub fn changelog_update(&self) -> bool {
+        self.changelog_update.unwrap_or(false)
+    }
+
+    pub fn changelog_path(&self) -> &Path {
+        self.changelog_path
+            .as_deref()
+            .unwrap_or_else(|| Path::new("CHANGELOG.md"))
+    }
+
+    pub fn public_dependencies(&self) -> &[String] {
+        self.public_dependencies
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    pub fn manifest_breaking(&self) -> ManifestBreaking {
+        self.manifest_breaking.unwrap_or_default()
+    }
+
+    pub fn commit_walk(&self) -> CommitWalk {
+        self.commit_walk.unwrap_or_default()
+    }
+
+    pub fn changes_include(&self) -> &[String] {
+        self.changes_include
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    pub fn changes_exclude(&self) -> &[String] {
+        self.changes_exclude
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    pub fn changes_ignore_authors(&self) -> &[String] {
+        self.changes_ignore_authors
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    pub fn changes_ignore_messages(&self) -> &[String] {
+        self.changes_ignore_messages
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    pub fn issue_url(&self) -> Option<&str> {
+        self.issue_url.as_deref()
+    }
+
+    /// Release impact of a conventional commit, preferring a `type(scope)` entry over `type`
+    pub fn commit_type(&self, type_: &str, scope: Option<&str>) -> Option<CommitStatus> {
+        let configured = |key: &str| {
+            self.commit_types
+                .iter()
+                .flatten()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, s)| *s)
+        };
+        if let Some(scope) = scope {
+            if let Some(status) = configured(&format!("{type_}({scope})")) {
+                return Some(status);
+            }
+        }
+        configured(type_).or_else(|| {
+            DEFAULT_COMMIT_TYPES
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(type_))
+                .map(|(_, s)| *s)
+        })
+    }
+
     pub fn tag_message(&self) -> &str {
         self.tag_message
             .as_deref()
@@ -307,10 +491,39 @@ impl Config {
         self.dependent_version.unwrap_or_default()
     }
 
+    pub fn cascade_bumps(&self) -> bool {
+        self.cascade_bumps.unwrap_or(false)
+    }
+
     pub fn metadata(&self) -> MetadataPolicy {
         self.metadata.unwrap_or_default()
     }
 
+    pub fn metadata_template(&self) -> &str {
+        self.metadata_template.as_deref().unwrap_or("{{short_sha}}")
+    }
+
+    pub fn version_scheme(&self) -> VersionScheme {
+        self.version_scheme.unwrap_or_default()
+    }
+
+    pub fn version_source(&self) -> VersionSource {
+        self.version_source.unwrap_or_default()
+    }
+
+    pub fn prerelease_channels(&self) -> impl Iterator<Item = &str> {
+        self.prerelease_channels
+            .as_deref()
+            .map(|c| itertools::Either::Left(c.iter().map(|s| s.as_str())))
+            .unwrap_or_else(|| {
+                itertools::Either::Right(
+                    crate::ops::version::DEFAULT_PRERELEASE_CHANNELS
+                        .iter()
+                        .copied(),
+                )
+            })
+    }
+
     pub fn certs_source(&self) -> CertsSource {
         self.certs_source.unwrap_or_default()
     }
@@ -370,6 +583,14 @@ pub struct Replace {
     pub prerelease: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VersionFile {
+    pub file: PathBuf,
+    /// Dot-separated key of the version within `file`
+    pub path: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Command {
@@ -386,6 +607,18 @@ impl Command {
     }
 }
 
+const DEFAULT_COMMIT_TYPES: &[(&str, CommitStatus)] = &[
+    ("chore", CommitStatus::Ignore),
+    ("test", CommitStatus::Ignore),
+    ("style", CommitStatus::Ignore),
+    ("refactor", CommitStatus::Ignore),
+    ("revert", CommitStatus::Ignore),
+    ("docs", CommitStatus::Fix),
+    ("perf", CommitStatus::Fix),
+    ("fix", CommitStatus::Fix),
+    ("feat", CommitStatus::Feature),
+];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
 #[serde(rename_all = "kebab-case")]
 #[value(rename_all = "kebab-case")]
@@ -396,6 +629,66 @@ pub enum DependentVersion {
     Upgrade,
     /// Upgrade when the old version requirement no longer applies
     Fix,
+    /// Fail when the old version requirement no longer applies
+    Error,
+    /// Warn when the old version requirement no longer applies
+    Warn,
+    /// Leave version requirements untouched
+    Ignore,
+    /// Always set an exact `=x.y.z` requirement
+    Pin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+#[derive(Default)]
+pub enum ManifestBreaking {
+    /// Fail the release
+    Error,
+    /// Report the changes and continue
+    #[default]
+    Warn,
+    /// Skip checking the manifest
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+#[derive(Default)]
+pub enum CommitWalk {
+    /// Every commit, skipping merge commits
+    #[default]
+    All,
+    /// Commits on the first-parent history, including merge commits
+    FirstParent,
+    /// Merge commits on the first-parent history
+    Merges,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+#[derive(Default)]
+pub enum VersionScheme {
+    /// `MAJOR.MINOR.PATCH`
+    #[default]
+    Semver,
+    /// `YEAR.MONTH.PATCH`; `major`, `minor`, and `patch` bump to today's date
+    Calver,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+#[derive(Default)]
+pub enum VersionSource {
+    /// The `package.version` in `Cargo.toml`
+    #[default]
+    Manifest,
+    /// The latest tag matching `tag-name`; the version is only written into the published package
+    Tag,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
@@ -424,6 +717,8 @@ pub enum MetadataPolicy {
     Ignore,
     /// Keep the prior metadata if not set
     Persistent,
+    /// Derive from git with `metadata-template` if not set
+    Auto,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1067,4 +1362,53 @@ mod test {
             assert!(!release_config.sign_commit());
         }
     }
+
+    mod commit_type {
+        use super::*;
+
+        #[test]
+        fn builtin() {
+            let config = Config::new();
+            assert_eq!(
+                config.commit_type("feat", None),
+                Some(CommitStatus::Feature)
+            );
+            assert_eq!(
+                config.commit_type("DOCS", Some("ui")),
+                Some(CommitStatus::Fix)
+            );
+            assert_eq!(config.commit_type("deps", None), None);
+        }
+
+        #[test]
+        fn overrides() {
+            let mut config = Config::new();
+            config.update(&Config {
+                commit_types: Some(
+                    [
+                        ("docs".to_owned(), CommitStatus::Ignore),
+                        ("security".to_owned(), CommitStatus::Fix),
+                        ("feat(ui)".to_owned(), CommitStatus::Fix),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ),
+                ..Default::default()
+            });
+            assert_eq!(config.commit_type("docs", None), Some(CommitStatus::Ignore));
+            assert_eq!(
+                config.commit_type("security", None),
+                Some(CommitStatus::Fix)
+            );
+            assert_eq!(
+                config.commit_type("feat", Some("ui")),
+                Some(CommitStatus::Fix)
+            );
+            assert_eq!(
+                config.commit_type("feat", Some("cli")),
+                Some(CommitStatus::Feature)
+            );
+            assert_eq!(config.commit_type("fix", None), Some(CommitStatus::Fix));
+        }
+    }
 }
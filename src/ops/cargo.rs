@@ -53,6 +53,7 @@ pub fn package_content(manifest_path: &Path) -> CargoResult<Vec<std::path::PathB
 #[allow(clippy::too_many_arguments)]
 pub fn publish(
     dry_run: bool,
+    allow_dirty: bool,
     verify: bool,
     manifest_path: &Path,
     pkgids: &[&str],
@@ -88,6 +89,8 @@ pub fn publish(
 
     if dry_run {
         command.push("--dry-run");
+    }
+    if dry_run || allow_dirty {
         command.push("--allow-dirty");
     }
 
@@ -282,6 +285,10 @@ pub fn upgrade_dependency_req(
     upgrade: config::DependentVersion,
     dry_run: bool,
 ) -> CargoResult<()> {
+    if upgrade == config::DependentVersion::Ignore {
+        return Ok(());
+    }
+
     let manifest_root = manifest_path
         .parent()
         .expect("always at least a parent dir");
@@ -292,7 +299,7 @@ pub fn upgrade_dependency_req(
         .flat_map(|t| t.iter_mut().filter_map(|(_, d)| d.as_table_like_mut()))
         .filter(|d| is_relevant(*d, manifest_root, root))
     {
-        upgrade_req(manifest_name, dep_item, name, version, upgrade);
+        upgrade_req(manifest_name, dep_item, name, version, upgrade)?;
     }
 
     let manifest = manifest.to_string();
@@ -375,49 +382,60 @@ fn upgrade_req(
     name: &str,
     version: &semver::Version,
     upgrade: config::DependentVersion,
-) -> bool {
+) -> CargoResult<bool> {
     let version_value = if let Some(version_value) = dep_item.get_mut("version") {
         version_value
     } else {
         log::debug!("not updating path-only dependency on {}", name);
-        return false;
+        return Ok(false);
     };
 
     let existing_req_str = if let Some(existing_req) = version_value.as_str() {
         existing_req
     } else {
         log::debug!("unsupported dependency {}", name);
-        return false;
+        return Ok(false);
     };
     let Ok(existing_req) = semver::VersionReq::parse(existing_req_str) else {
         log::debug!("unsupported dependency req {}={}", name, existing_req_str);
-        return false;
+        return Ok(false);
     };
     let new_req = match upgrade {
         config::DependentVersion::Fix => {
-            if !existing_req.matches(version) {
-                let new_req = crate::ops::version::upgrade_requirement(existing_req_str, version)
-                    .ok()
-                    .flatten();
-                if let Some(new_req) = new_req {
-                    new_req
-                } else {
-                    return false;
-                }
-            } else {
-                return false;
+            if existing_req.matches(version) {
+                return Ok(false);
             }
+            crate::ops::version::widen_requirement(existing_req_str, version).map_err(|err| {
+                anyhow::format_err!("{manifest_name}'s dependency on {name}: {err}")
+            })?
         }
         config::DependentVersion::Upgrade => {
-            let new_req = crate::ops::version::upgrade_requirement(existing_req_str, version)
-                .ok()
-                .flatten();
-            if let Some(new_req) = new_req {
-                new_req
-            } else {
-                return false;
+            crate::ops::version::upgrade_requirement(existing_req_str, version).map_err(|err| {
+                anyhow::format_err!("{manifest_name}'s dependency on {name}: {err}")
+            })?
+        }
+        config::DependentVersion::Error => {
+            // Mismatches are reported by `verify_dependent_versions` before anything is written
+            return Ok(false);
+        }
+        config::DependentVersion::Warn => {
+            if !existing_req.matches(version) {
+                let _ = crate::ops::shell::warn(format!(
+                    "{manifest_name}'s dependency on {name} ({existing_req_str}) does not match {version}"
+                ));
             }
+            return Ok(false);
+        }
+        config::DependentVersion::Ignore => {
+            return Ok(false);
         }
+        config::DependentVersion::Pin => {
+            let pin = pin_requirement(version);
+            (pin != existing_req_str).then_some(pin)
+        }
+    };
+    let Some(new_req) = new_req else {
+        return Ok(false);
     };
 
     let _ = crate::ops::shell::status(
@@ -425,7 +443,120 @@ fn upgrade_req(
         format!("{manifest_name}'s dependency from {existing_req_str} to {new_req}"),
     );
     *version_value = toml_edit::value(new_req);
-    true
+    Ok(true)
+}
+
+fn pin_requirement(version: &semver::Version) -> String {
+    semver::Comparator {
+        op: semver::Op::Exact,
+        major: version.major,
+        minor: Some(version.minor),
+        patch: Some(version.patch),
+        pre: version.pre.clone(),
+    }
+    .to_string()
+}
+
+/// Describe requirements on `name` that are out of sync with `version` under `upgrade`
+pub fn check_dependency_req(
+    manifest_name: &str,
+    manifest_path: &Path,
+    root: &Path,
+    name: &str,
+    version: &semver::Version,
+    upgrade: config::DependentVersion,
+) -> CargoResult<Vec<String>> {
+    let manifest_root = manifest_path
+        .parent()
+        .expect("always at least a parent dir");
+    let mut manifest: toml_edit::DocumentMut = std::fs::read_to_string(manifest_path)?.parse()?;
+
+    let mut problems = Vec::new();
+    for dep_item in find_dependency_tables(manifest.as_table_mut())
+        .flat_map(|t| t.iter_mut().filter_map(|(_, d)| d.as_table_like_mut()))
+        .filter(|d| is_relevant(*d, manifest_root, root))
+    {
+        let Some(existing_req_str) = dep_item.get("version").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let Ok(existing_req) = semver::VersionReq::parse(existing_req_str) else {
+            continue;
+        };
+        let prefix = format!("{manifest_name}'s dependency on {name} ({existing_req_str})");
+        if !existing_req.matches(version) {
+            problems.push(format!("{prefix} does not match {version}"));
+            continue;
+        }
+        match upgrade {
+            config::DependentVersion::Upgrade => {
+                if let Ok(Some(new_req)) =
+                    crate::ops::version::upgrade_requirement(existing_req_str, version)
+                {
+                    problems.push(format!("{prefix} is behind {version}, expected {new_req}"));
+                }
+            }
+            config::DependentVersion::Pin => {
+                let pin = pin_requirement(version);
+                if pin != existing_req_str {
+                    problems.push(format!("{prefix} is not pinned, expected {pin}"));
+                }
+            }
+            config::DependentVersion::Fix
+            | config::DependentVersion::Error
+            | config::DependentVersion::Warn
+            | config::DependentVersion::Ignore => {}
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Report dependency requirements that `fix` or `upgrade` can't rewrite to match `version`
+pub fn check_dependency_rewrite(
+    manifest_name: &str,
+    manifest_path: &Path,
+    root: &Path,
+    name: &str,
+    version: &semver::Version,
+    upgrade: config::DependentVersion,
+) -> CargoResult<Vec<String>> {
+    let manifest_root = manifest_path
+        .parent()
+        .expect("always at least a parent dir");
+    let mut manifest: toml_edit::DocumentMut = std::fs::read_to_string(manifest_path)?.parse()?;
+
+    let mut problems = Vec::new();
+    for dep_item in find_dependency_tables(manifest.as_table_mut())
+        .flat_map(|t| t.iter_mut().filter_map(|(_, d)| d.as_table_like_mut()))
+        .filter(|d| is_relevant(*d, manifest_root, root))
+    {
+        let Some(existing_req_str) = dep_item.get("version").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let Ok(existing_req) = semver::VersionReq::parse(existing_req_str) else {
+            continue;
+        };
+        let rewritten = match upgrade {
+            config::DependentVersion::Fix => {
+                if existing_req.matches(version) {
+                    continue;
+                }
+                crate::ops::version::widen_requirement(existing_req_str, version)
+            }
+            config::DependentVersion::Upgrade => {
+                crate::ops::version::upgrade_requirement(existing_req_str, version)
+            }
+            config::DependentVersion::Error
+            | config::DependentVersion::Warn
+            | config::DependentVersion::Ignore
+            | config::DependentVersion::Pin => continue,
+        };
+        if let Err(err) = rewritten {
+            problems.push(format!("{manifest_name}'s dependency on {name}: {err}"));
+        }
+    }
+
+    Ok(problems)
 }
 
 pub fn update_lock(manifest_path: &Path) -> CargoResult<()> {
@@ -648,4 +779,65 @@ mod test {
             temp.close().unwrap();
         }
     }
+
+    mod upgrade_req {
+        use super::*;
+
+        fn upgrade(
+            req: &str,
+            version: &str,
+            upgrade: config::DependentVersion,
+        ) -> CargoResult<String> {
+            let mut manifest: toml_edit::DocumentMut =
+                format!("dep = {{ version = \"{req}\", path = \"../dep\" }}\n")
+                    .parse()
+                    .unwrap();
+            let dep_item = manifest["dep"].as_table_like_mut().unwrap();
+            let version = semver::Version::parse(version).unwrap();
+            upgrade_req("foo", dep_item, "dep", &version, upgrade)?;
+            Ok(manifest["dep"]["version"].as_str().unwrap().to_owned())
+        }
+
+        #[test]
+        fn error() {
+            let actual = upgrade("1.0", "1.2.0", config::DependentVersion::Error).unwrap();
+            assert_eq!(actual, "1.0");
+            let actual = upgrade("1.0", "2.0.0", config::DependentVersion::Error).unwrap();
+            assert_eq!(actual, "1.0");
+        }
+
+        #[test]
+        fn warn() {
+            let actual = upgrade("1.0", "2.0.0", config::DependentVersion::Warn).unwrap();
+            assert_eq!(actual, "1.0");
+        }
+
+        #[test]
+        fn ignore() {
+            let actual = upgrade("1.0", "2.0.0", config::DependentVersion::Ignore).unwrap();
+            assert_eq!(actual, "1.0");
+        }
+
+        #[test]
+        fn pin() {
+            let actual = upgrade("1.0", "1.2.0", config::DependentVersion::Pin).unwrap();
+            assert_eq!(actual, "=1.2.0");
+            let actual = upgrade("=1.2.0", "1.2.0+build.5", config::DependentVersion::Pin).unwrap();
+            assert_eq!(actual, "=1.2.0");
+            let actual = upgrade("1.0", "2.0.0-rc.1", config::DependentVersion::Pin).unwrap();
+            assert_eq!(actual, "=2.0.0-rc.1");
+        }
+
+        #[test]
+        fn fix() {
+            let actual = upgrade("1.0", "1.2.0", config::DependentVersion::Fix).unwrap();
+            assert_eq!(actual, "1.0");
+            let actual = upgrade("1.0", "2.0.0", config::DependentVersion::Fix).unwrap();
+            assert_eq!(actual, "2.0");
+            let actual = upgrade(">=1.2, <2", "2.0.0", config::DependentVersion::Fix).unwrap();
+            assert_eq!(actual, ">=1.2, <3");
+            let actual = upgrade(">=1.2, <2", "2.0.0", config::DependentVersion::Upgrade).unwrap();
+            assert_eq!(actual, ">=2.0, <3");
+        }
+    }
 }
@@ -0,0 +1,210 @@
+/// Keep-a-Changelog section a change is listed under
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Section {
+    Breaking,
+    Added,
+    Changed,
+    Fixed,
+}
+
+impl Section {
+    pub fn title(self) -> &'static str {
+        match self {
+            Section::Breaking => "Breaking",
+            Section::Added => "Added",
+            Section::Changed => "Changed",
+            Section::Fixed => "Fixed",
+        }
+    }
+}
+
+/// Render a `## [version] - date` section, grouping `entries` by [`Section`] and crediting
+/// `contributors`
+pub fn render_release(
+    version: &str,
+    date: &str,
+    entries: &[(Section, String)],
+    contributors: &[String],
+) -> String {
+    let mut release = format!("## [{version}] - {date}\n");
+    release.push_str(&render_sections(entries));
+    if !contributors.is_empty() {
+        release.push_str(&format!("\nContributors: {}\n", contributors.join(", ")));
+    }
+    release
+}
+
+/// Render `### Section` lists for `entries`, in [`Section`] order
+pub fn render_sections(entries: &[(Section, String)]) -> String {
+    let mut release = String::new();
+    let mut sections = entries.iter().map(|(s, _)| *s).collect::<Vec<_>>();
+    sections.sort_unstable();
+    sections.dedup();
+    for section in sections {
+        release.push_str(&format!("\n### {}\n\n", section.title()));
+        for (_, entry) in entries.iter().filter(|(s, _)| *s == section) {
+            release.push_str(&format!("- {entry}\n"));
+        }
+    }
+
+    release
+}
+
+/// Insert a rendered release section before the most recent release
+///
+/// An `## [Unreleased]` section is kept at the top.
+pub fn insert_release(changelog: &str, release: &str) -> String {
+    if changelog.trim().is_empty() {
+        return format!("# Changelog\n\n{release}");
+    }
+
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        if line.starts_with("## ") && !line.to_lowercase().starts_with("## [unreleased]") {
+            let (head, tail) = changelog.split_at(offset);
+            return format!("{head}{release}\n{tail}");
+        }
+        offset += line.len();
+    }
+
+    let separator = if changelog.ends_with("\n\n") {
+        ""
+    } else if changelog.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!("{changelog}{separator}{release}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod render_release {
+        use super::*;
+
+        #[test]
+        fn groups_sections() {
+            let entries = vec![
+                (Section::Fixed, "Don't panic".to_owned()),
+                (Section::Added, "New flag".to_owned()),
+                (Section::Breaking, "Removed flag".to_owned()),
+                (Section::Fixed, "Don't crash".to_owned()),
+            ];
+            let actual = render_release("1.0.0", "2024-01-01", &entries, &[]);
+            assert_eq!(
+                actual,
+                "## [1.0.0] - 2024-01-01
+
+### Breaking
+
+- Removed flag
+
+### Added
+
+- New flag
+
+### Fixed
+
+- Don't panic
+- Don't crash
+"
+            );
+        }
+
+        #[test]
+        fn no_entries() {
+            let actual = render_release("1.0.0", "2024-01-01", &[], &[]);
+            assert_eq!(actual, "## [1.0.0] - 2024-01-01\n");
+        }
+
+        #[test]
+        fn contributors() {
+            let entries = vec![(Section::Fixed, "Don't panic".to_owned())];
+            let contributors = vec!["Ada".to_owned(), "Grace".to_owned()];
+            let actual = render_release("1.0.0", "2024-01-01", &entries, &contributors);
+            assert_eq!(
+                actual,
+                "## [1.0.0] - 2024-01-01
+
+### Fixed
+
+- Don't panic
+
+Contributors: Ada, Grace
+"
+            );
+        }
+    }
+
+    mod insert_release {
+        use super::*;
+
+        const RELEASE: &str = "## [1.1.0] - 2024-02-01\n\n### Fixed\n\n- Bug\n";
+
+        #[test]
+        fn empty() {
+            let actual = insert_release("", RELEASE);
+            assert_eq!(actual, format!("# Changelog\n\n{RELEASE}"));
+        }
+
+        #[test]
+        fn before_prior_release() {
+            let changelog = "# Changelog
+
+## [1.0.0] - 2024-01-01
+
+- Initial release
+";
+            let actual = insert_release(changelog, RELEASE);
+            assert_eq!(
+                actual,
+                "# Changelog
+
+## [1.1.0] - 2024-02-01
+
+### Fixed
+
+- Bug
+
+## [1.0.0] - 2024-01-01
+
+- Initial release
+"
+            );
+        }
+
+        #[test]
+        fn after_unreleased() {
+            let changelog = "# Changelog
+
+## [Unreleased]
+
+## [1.0.0] - 2024-01-01
+";
+            let actual = insert_release(changelog, RELEASE);
+            assert_eq!(
+                actual,
+                "# Changelog
+
+## [Unreleased]
+
+## [1.1.0] - 2024-02-01
+
+### Fixed
+
+- Bug
+
+## [1.0.0] - 2024-01-01
+"
+            );
+        }
+
+        #[test]
+        fn no_prior_release() {
+            let actual = insert_release("# Changelog\n", RELEASE);
+            assert_eq!(actual, format!("# Changelog\n\n{RELEASE}"));
+        }
+    }
+}
@@ -137,6 +137,106 @@ pub fn changed_files(dir: &Path, tag: &str) -> CargoResult<Option<Vec<PathBuf>>>
     }
 }
 
+/// Uncommitted paths, staged or not, including untracked files
+pub fn dirty_files(dir: &Path) -> CargoResult<Vec<PathBuf>> {
+    let repo = git2::Repository::discover(dir)?;
+    let Some(root) = repo.workdir() else {
+        return Ok(Vec::new());
+    };
+
+    let mut options = git2::StatusOptions::new();
+    options
+        .show(git2::StatusShow::IndexAndWorkdir)
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut options))?;
+    let paths = statuses
+        .iter()
+        .map(|status| root.join(bytes2path(status.path_bytes())))
+        .collect();
+    Ok(paths)
+}
+
+/// A commit reachable from `HEAD` but not the starting point of [`walk_commits`]
+#[derive(Clone, Debug)]
+pub struct WalkedCommit {
+    pub id: git2::Oid,
+    pub short_id: String,
+    pub summary: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Changed paths, relative to the repository root
+    pub paths: Vec<PathBuf>,
+}
+
+/// Commits in `since_ref..HEAD`, diffed against their first parent
+pub fn walk_commits(
+    dir: &Path,
+    since_ref: &str,
+    walk: crate::config::CommitWalk,
+) -> CargoResult<Vec<WalkedCommit>> {
+    let repo = git2::Repository::discover(dir)?;
+
+    let since_id = repo
+        .revparse_single(since_ref)
+        .and_then(|o| o.peel_to_commit())
+        .map_err(|_| anyhow::format_err!("could not find {}", since_ref))?
+        .id();
+    let head_id = repo.head()?.peel_to_commit()?.id();
+
+    let mut revwalk = repo.revwalk()?;
+    if walk != crate::config::CommitWalk::All {
+        revwalk.simplify_first_parent()?;
+    }
+    revwalk.push_range(&format!("{since_id}..{head_id}"))?;
+
+    let mut commits = Vec::new();
+    for commit_id in revwalk {
+        let commit_id = commit_id?;
+        let commit = repo.find_commit(commit_id)?;
+        let is_merge = 1 < commit.parent_count();
+        match walk {
+            crate::config::CommitWalk::All if is_merge => {
+                // Assuming merge commits can be ignored
+                continue;
+            }
+            crate::config::CommitWalk::Merges if !is_merge => {
+                continue;
+            }
+            _ => {}
+        }
+        let parent_tree = commit.parent(0).ok().map(|c| c.tree()).transpose()?;
+        let tree = commit.tree()?;
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+
+        let mut paths = Vec::new();
+        for delta in diff.deltas() {
+            let old_path = delta.old_file().path();
+            let new_path = delta.new_file().path();
+            for path in [old_path, new_path].into_iter().flatten() {
+                if !paths.iter().any(|p| p == path) {
+                    paths.push(path.to_owned());
+                }
+            }
+        }
+
+        let short_id =
+            String::from_utf8_lossy(&repo.find_object(commit_id, None)?.short_id()?).into_owned();
+        commits.push(WalkedCommit {
+            id: commit_id,
+            short_id,
+            summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or(b"")).into_owned(),
+            message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+            author_name: String::from_utf8_lossy(commit.author().name_bytes()).into_owned(),
+            author_email: String::from_utf8_lossy(commit.author().email_bytes()).into_owned(),
+            paths,
+        });
+    }
+
+    Ok(commits)
+}
+
 pub fn commit_all(dir: &Path, msg: &str, sign: bool, dry_run: bool) -> CargoResult<bool> {
     let repo = git2::Repository::discover(dir)?;
     let mut options = git2::StatusOptions::new();
@@ -158,6 +258,14 @@ pub fn commit_all(dir: &Path, msg: &str, sign: bool, dry_run: bool) -> CargoResu
     }
 }
 
+pub fn add(dir: &Path, path: &Path, dry_run: bool) -> CargoResult<bool> {
+    call_on_path(
+        vec!["git", "add", "--", path.to_str().unwrap_or_default()],
+        dir,
+        dry_run,
+    )
+}
+
 pub fn tag(dir: &Path, name: &str, msg: &str, sign: bool, dry_run: bool) -> CargoResult<bool> {
     let mut cmd = vec!["git", "tag", name];
     if !msg.is_empty() {
@@ -169,6 +277,39 @@ pub fn tag(dir: &Path, name: &str, msg: &str, sign: bool, dry_run: bool) -> Carg
     call_on_path(cmd, dir, dry_run)
 }
 
+/// The commit `HEAD` points at
+#[derive(Clone, Debug)]
+pub struct HeadCommit {
+    pub id: git2::Oid,
+    /// `id` abbreviated per the repository's `core.abbrev`
+    pub short_id: String,
+    pub time: git2::Time,
+}
+
+pub fn head_commit(dir: &Path) -> CargoResult<HeadCommit> {
+    let repo = git2::Repository::discover(dir)?;
+    let commit = repo.head()?.peel_to_commit()?;
+    let short_id = String::from_utf8_lossy(&commit.as_object().short_id()?).into_owned();
+    Ok(HeadCommit {
+        id: commit.id(),
+        short_id,
+        time: commit.time(),
+    })
+}
+
+/// Whether `HEAD` is, or descends from, `rev`
+pub fn head_descends_from(dir: &Path, rev: &str) -> CargoResult<bool> {
+    let repo = git2::Repository::discover(dir)?;
+    let head = repo.head()?.peel_to_commit()?.id();
+    let rev = repo.revparse_single(rev)?.peel_to_commit()?.id();
+    Ok(head == rev || repo.graph_descendant_of(head, rev)?)
+}
+
+/// Blob id `path` would have if committed
+pub fn hash_file(path: &Path) -> CargoResult<git2::Oid> {
+    Ok(git2::Oid::hash_file(git2::ObjectType::Blob, path)?)
+}
+
 pub fn tag_exists(dir: &Path, name: &str) -> CargoResult<bool> {
     let repo = git2::Repository::discover(dir)?;
 
@@ -176,7 +317,28 @@ pub fn tag_exists(dir: &Path, name: &str) -> CargoResult<bool> {
     Ok(!names.is_empty())
 }
 
-pub fn find_last_tag(dir: &Path, glob: &globset::GlobMatcher) -> Option<String> {
+/// Contents of `path` as of `rev`, if it existed
+pub fn show_file(dir: &Path, rev: &str, path: &Path) -> CargoResult<Option<String>> {
+    let repo = git2::Repository::discover(dir)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow::format_err!("bare repositories are unsupported"))?;
+    let workdir = dunce::canonicalize(workdir)?;
+    let path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+    let relpath = path.strip_prefix(&workdir)?;
+
+    let tree = repo.revparse_single(rev)?.peel_to_tree()?;
+    let entry = match tree.get_path(relpath) {
+        Ok(entry) => entry,
+        Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let blob = entry.to_object(&repo)?.peel_to_blob()?;
+    Ok(Some(String::from_utf8_lossy(blob.content()).into_owned()))
+}
+
+/// Find the closest tag matching `glob`, only considering annotated tags unless `lightweight`
+pub fn find_last_tag(dir: &Path, glob: &globset::GlobMatcher, lightweight: bool) -> Option<String> {
     let repo = git2::Repository::discover(dir).ok()?;
     let mut tags: std::collections::HashMap<git2::Oid, String> = Default::default();
     repo.tag_foreach(|id, name| {
@@ -184,8 +346,11 @@ pub fn find_last_tag(dir: &Path, glob: &globset::GlobMatcher) -> Option<String>
         let name = name.strip_prefix("refs/tags/").unwrap_or(&name);
         if glob.is_match(name) {
             let name = name.to_owned();
-            let tag = repo.find_tag(id);
-            let target = tag.and_then(|t| t.target());
+            let target = if lightweight {
+                repo.find_object(id, None)
+            } else {
+                repo.find_tag(id).and_then(|t| t.target())
+            };
             let commit = target.and_then(|t| t.peel_to_commit());
             if let Ok(commit) = commit {
                 tags.insert(commit.id(), name);
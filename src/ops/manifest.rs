@@ -0,0 +1,336 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::error::CargoResult;
+
+/// The parts of a package manifest that dependents rely on
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestApi {
+    pub version: Option<semver::Version>,
+    pub rust_version: Option<String>,
+    pub features: BTreeSet<String>,
+    pub dependencies: BTreeMap<String, Dependency>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dependency {
+    /// The `package` a renamed dependency refers to
+    pub package: Option<String>,
+    pub req: Option<String>,
+    pub public: bool,
+}
+
+impl ManifestApi {
+    /// Parse a package manifest, resolving `workspace = true` fields through `workspace`
+    pub fn parse(manifest: &str, workspace: Option<&str>) -> CargoResult<Self> {
+        let manifest: toml::Table = manifest.parse()?;
+        let workspace: Option<toml::Table> = workspace.map(|w| w.parse()).transpose()?;
+        let ws_package = workspace
+            .as_ref()
+            .and_then(|w| w.get("workspace"))
+            .and_then(|w| w.get("package"));
+        let ws_dependencies = workspace
+            .as_ref()
+            .and_then(|w| w.get("workspace"))
+            .and_then(|w| w.get("dependencies"));
+
+        let package = manifest.get("package");
+        let package_field = |name: &str| {
+            let value = package.and_then(|p| p.get(name))?;
+            if is_inherited(value) {
+                ws_package.and_then(|p| p.get(name))?.as_str()
+            } else {
+                value.as_str()
+            }
+        };
+        let version = package_field("version")
+            .map(semver::Version::parse)
+            .transpose()?;
+        let rust_version = package_field("rust-version").map(|s| s.to_owned());
+
+        let features = manifest
+            .get("features")
+            .and_then(|f| f.as_table())
+            .map(|f| f.keys().cloned().collect())
+            .unwrap_or_default();
+
+        let targets = manifest
+            .get("target")
+            .and_then(|t| t.as_table())
+            .into_iter()
+            .flat_map(|t| t.values());
+        let dependencies = std::iter::once(manifest.get("dependencies"))
+            .chain(targets.map(|t| t.get("dependencies")))
+            .filter_map(|d| d?.as_table())
+            .flat_map(|deps| deps.iter())
+            .map(|(name, dep)| {
+                let mut req = dep.as_str().or_else(|| dep.get("version")?.as_str());
+                if is_inherited(dep) {
+                    let ws_dep = ws_dependencies.and_then(|d| d.get(name));
+                    req = ws_dep.and_then(|d| d.as_str().or_else(|| d.get("version")?.as_str()));
+                }
+                let public = dep.get("public").and_then(|p| p.as_bool()).unwrap_or(false);
+                let package = dep.get("package").and_then(|p| p.as_str());
+                let dep = Dependency {
+                    package: package.map(|p| p.to_owned()),
+                    req: req.map(|r| r.to_owned()),
+                    public,
+                };
+                (name.clone(), dep)
+            })
+            .collect();
+
+        Ok(Self {
+            version,
+            rust_version,
+            features,
+            dependencies,
+        })
+    }
+
+    /// Look up the dependency on the package `name`, whether or not it was renamed
+    pub fn dependency(&self, name: &str) -> Option<&Dependency> {
+        self.dependencies
+            .iter()
+            .find(|(key, dep)| dep.package.as_deref().unwrap_or(key) == name)
+            .map(|(_, dep)| dep)
+    }
+}
+
+fn is_inherited(value: &toml::Value) -> bool {
+    value
+        .get("workspace")
+        .and_then(|w| w.as_bool())
+        .unwrap_or(false)
+}
+
+/// Changes between manifests that break dependents
+///
+/// `public_dependencies` are treated as if marked `public = true`.
+pub fn breaking_changes(
+    old: &ManifestApi,
+    new: &ManifestApi,
+    public_dependencies: &[String],
+) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for feature in old.features.difference(&new.features) {
+        changes.push(format!("removed feature `{feature}`"));
+    }
+
+    if let (Some(old_msrv), Some(new_msrv)) = (&old.rust_version, &new.rust_version) {
+        if parse_partial(old_msrv) < parse_partial(new_msrv) {
+            changes.push(format!("raised rust-version from {old_msrv} to {new_msrv}"));
+        }
+    } else if let (None, Some(new_msrv)) = (&old.rust_version, &new.rust_version) {
+        changes.push(format!("set rust-version to {new_msrv}"));
+    }
+
+    for (name, new_dep) in &new.dependencies {
+        if !new_dep.public && !public_dependencies.contains(name) {
+            continue;
+        }
+        let Some(old_dep) = old.dependencies.get(name) else {
+            continue;
+        };
+        let (Some(old_req), Some(new_req)) = (old_dep.req.as_deref(), new_dep.req.as_deref())
+        else {
+            continue;
+        };
+        let (Some(old_min), Some(new_min)) = (min_version(old_req), min_version(new_req)) else {
+            continue;
+        };
+        if !is_compatible(&old_min, &new_min) {
+            changes.push(format!(
+                "changed public dependency `{name}` from {old_req} to {new_req}"
+            ));
+        }
+    }
+
+    changes
+}
+
+/// Whether `new` can be used where `old` was required, per Cargo's semver rules
+pub fn is_compatible(old: &semver::Version, new: &semver::Version) -> bool {
+    fn compat_key(version: &semver::Version) -> (u64, u64, u64) {
+        match (version.major, version.minor, version.patch) {
+            (0, 0, patch) => (0, 0, patch),
+            (0, minor, _) => (0, minor, 0),
+            (major, _, _) => (major, 0, 0),
+        }
+    }
+
+    compat_key(old) == compat_key(new)
+}
+
+fn min_version(req: &str) -> Option<semver::Version> {
+    let req = semver::VersionReq::parse(req).ok()?;
+    let comparator = req.comparators.first()?;
+    Some(semver::Version::new(
+        comparator.major,
+        comparator.minor.unwrap_or(0),
+        comparator.patch.unwrap_or(0),
+    ))
+}
+
+fn parse_partial(version: &str) -> Vec<u64> {
+    version.split('.').map(|p| p.parse().unwrap_or(0)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn inherits_from_workspace() {
+            let workspace = r#"
+[workspace.package]
+version = "1.2.3"
+rust-version = "1.70"
+
+[workspace.dependencies]
+serde = { version = "1.0.100", features = ["derive"] }
+"#;
+            let manifest = r#"
+[package]
+name = "foo"
+version.workspace = true
+rust-version.workspace = true
+
+[features]
+default = []
+std = []
+
+[dependencies]
+serde = { workspace = true, public = true }
+log = "0.4"
+"#;
+            let actual = ManifestApi::parse(manifest, Some(workspace)).unwrap();
+            assert_eq!(actual.version, Some(semver::Version::new(1, 2, 3)));
+            assert_eq!(actual.rust_version.as_deref(), Some("1.70"));
+            assert_eq!(
+                actual
+                    .features
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>(),
+                ["default", "std"]
+            );
+            assert_eq!(
+                actual.dependencies["serde"],
+                Dependency {
+                    package: None,
+                    req: Some("1.0.100".to_owned()),
+                    public: true,
+                }
+            );
+            assert_eq!(
+                actual.dependencies["log"],
+                Dependency {
+                    package: None,
+                    req: Some("0.4".to_owned()),
+                    public: false,
+                }
+            );
+        }
+    }
+
+    mod dependency {
+        use super::*;
+
+        #[test]
+        fn renamed() {
+            let manifest = r#"
+[dependencies]
+foo2 = { package = "foo", version = "2.0", public = true }
+bar = "1.0"
+"#;
+            let actual = ManifestApi::parse(manifest, None).unwrap();
+            assert!(actual.dependency("foo").is_some_and(|d| d.public));
+            assert!(actual.dependency("foo2").is_none());
+            assert!(actual.dependency("bar").is_some_and(|d| !d.public));
+        }
+    }
+
+    mod breaking_changes {
+        use super::*;
+
+        fn api(manifest: &str) -> ManifestApi {
+            ManifestApi::parse(manifest, None).unwrap()
+        }
+
+        #[test]
+        fn unchanged() {
+            let manifest = r#"
+[package]
+rust-version = "1.70"
+
+[features]
+std = []
+
+[dependencies]
+serde = { version = "1.0", public = true }
+"#;
+            let actual = breaking_changes(&api(manifest), &api(manifest), &[]);
+            assert!(actual.is_empty(), "{actual:?}");
+        }
+
+        #[test]
+        fn removed_feature() {
+            let old = api("[features]\nstd = []\nalloc = []\n");
+            let new = api("[features]\nstd = []\n");
+            let actual = breaking_changes(&old, &new, &[]);
+            assert_eq!(actual, ["removed feature `alloc`"]);
+        }
+
+        #[test]
+        fn raised_rust_version() {
+            let old = api("[package]\nrust-version = \"1.70\"\n");
+            let new = api("[package]\nrust-version = \"1.74.1\"\n");
+            let actual = breaking_changes(&old, &new, &[]);
+            assert_eq!(actual, ["raised rust-version from 1.70 to 1.74.1"]);
+
+            let actual = breaking_changes(&new, &old, &[]);
+            assert!(actual.is_empty(), "{actual:?}");
+        }
+
+        #[test]
+        fn public_dependency() {
+            let old = api("[dependencies]\nserde = \"1.0\"\nlog = \"0.3\"\n");
+            let new = api("[dependencies]\nserde = \"2.0\"\nlog = \"0.4\"\n");
+            let actual = breaking_changes(&old, &new, &[]);
+            assert!(actual.is_empty(), "{actual:?}");
+
+            let actual = breaking_changes(&old, &new, &["serde".to_owned()]);
+            assert_eq!(
+                actual,
+                ["changed public dependency `serde` from 1.0 to 2.0"]
+            );
+        }
+
+        #[test]
+        fn compatible_public_dependency() {
+            let old = api("[dependencies]\nserde = { version = \"1.0\", public = true }\n");
+            let new = api("[dependencies]\nserde = { version = \"1.0.200\", public = true }\n");
+            let actual = breaking_changes(&old, &new, &[]);
+            assert!(actual.is_empty(), "{actual:?}");
+        }
+    }
+
+    mod is_compatible {
+        use super::*;
+
+        #[test]
+        fn by_leftmost_nonzero() {
+            let v = |s| semver::Version::parse(s).unwrap();
+            assert!(is_compatible(&v("1.2.3"), &v("1.5.0")));
+            assert!(!is_compatible(&v("1.2.3"), &v("2.0.0")));
+            assert!(is_compatible(&v("0.2.3"), &v("0.2.4")));
+            assert!(!is_compatible(&v("0.2.3"), &v("0.3.0")));
+            assert!(!is_compatible(&v("0.0.3"), &v("0.0.4")));
+        }
+    }
+}
@@ -1,9 +1,12 @@
 pub mod cargo;
+pub mod changelog;
 pub mod cmd;
 pub mod git;
 pub mod index;
+pub mod manifest;
 pub mod replace;
 pub mod shell;
 pub mod version;
+pub mod version_file;
 
 pub(crate) mod diff;
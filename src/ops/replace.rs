@@ -19,8 +19,14 @@ pub struct Template<'a> {
     pub crate_name: Option<&'a str>,
     pub date: Option<&'a str>,
 
+    pub sha: Option<&'a str>,
+    pub short_sha: Option<&'a str>,
+    pub commit_date: Option<&'a str>,
+
     pub prefix: Option<&'a str>,
     pub tag_name: Option<&'a str>,
+    pub contributors: Option<&'a str>,
+    pub references: Option<&'a str>,
 }
 
 impl Template<'_> {
@@ -32,8 +38,14 @@ impl Template<'_> {
         const CRATE_NAME: &str = "{{crate_name}}";
         const DATE: &str = "{{date}}";
 
+        const SHA: &str = "{{sha}}";
+        const SHORT_SHA: &str = "{{short_sha}}";
+        const COMMIT_DATE: &str = "{{commit_date}}";
+
         const PREFIX: &str = "{{prefix}}";
         const TAG_NAME: &str = "{{tag_name}}";
+        const CONTRIBUTORS: &str = "{{contributors}}";
+        const REFERENCES: &str = "{{references}}";
 
         let mut s = input.to_owned();
         s = render_var(s, PREV_VERSION, self.prev_version);
@@ -43,8 +55,14 @@ impl Template<'_> {
         s = render_var(s, CRATE_NAME, self.crate_name);
         s = render_var(s, DATE, self.date);
 
+        s = render_var(s, SHA, self.sha);
+        s = render_var(s, SHORT_SHA, self.short_sha);
+        s = render_var(s, COMMIT_DATE, self.commit_date);
+
         s = render_var(s, PREFIX, self.prefix);
         s = render_var(s, TAG_NAME, self.tag_name);
+        s = render_var(s, CONTRIBUTORS, self.contributors);
+        s = render_var(s, REFERENCES, self.references);
         s
     }
 }
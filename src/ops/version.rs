@@ -28,6 +28,12 @@ pub trait VersionExt {
     ///
     /// Errors if this would decrement the pre-release phase.
     fn increment_rc(&mut self) -> CargoResult<()>;
+    /// Increment the `id` pre-release number for this Version.
+    ///
+    /// If this isn't `id`, switch to it.
+    ///
+    /// Errors if this would move to an earlier entry in `channels`.
+    fn increment_prerelease(&mut self, id: &str, channels: &[&str]) -> CargoResult<()>;
     /// Append informational-only metadata.
     fn metadata(&mut self, metadata: &str) -> CargoResult<()>;
     /// Checks to see if the current Version is in pre-release status
@@ -57,63 +63,37 @@ impl VersionExt for semver::Version {
     }
 
     fn increment_alpha(&mut self) -> CargoResult<()> {
-        if let Some((pre_ext, pre_ext_ver)) = prerelease_id_version(self)? {
-            if pre_ext == VERSION_BETA || pre_ext == VERSION_RC {
-                Err(anyhow::format_err!(
-                    "unsupported release level {}, only major, minor, and patch are supported",
-                    VERSION_ALPHA
-                ))
-            } else {
-                let new_ext_ver = if pre_ext == VERSION_ALPHA {
-                    pre_ext_ver.unwrap_or(0) + 1
-                } else {
-                    1
-                };
-                self.pre = semver::Prerelease::new(&format!("{VERSION_ALPHA}.{new_ext_ver}"))?;
-                Ok(())
-            }
-        } else {
-            self.increment_patch();
-            self.pre = semver::Prerelease::new(&format!("{VERSION_ALPHA}.1"))?;
-            Ok(())
-        }
+        self.increment_prerelease(VERSION_ALPHA, DEFAULT_PRERELEASE_CHANNELS)
     }
 
     fn increment_beta(&mut self) -> CargoResult<()> {
-        if let Some((pre_ext, pre_ext_ver)) = prerelease_id_version(self)? {
-            if pre_ext == VERSION_RC {
-                Err(anyhow::format_err!(
-                    "unsupported release level {}, only major, minor, and patch are supported",
-                    VERSION_BETA
-                ))
-            } else {
-                let new_ext_ver = if pre_ext == VERSION_BETA {
-                    pre_ext_ver.unwrap_or(0) + 1
-                } else {
-                    1
-                };
-                self.pre = semver::Prerelease::new(&format!("{VERSION_BETA}.{new_ext_ver}"))?;
-                Ok(())
-            }
-        } else {
-            self.increment_patch();
-            self.pre = semver::Prerelease::new(&format!("{VERSION_BETA}.1"))?;
-            Ok(())
-        }
+        self.increment_prerelease(VERSION_BETA, DEFAULT_PRERELEASE_CHANNELS)
     }
 
     fn increment_rc(&mut self) -> CargoResult<()> {
-        if let Some((pre_ext, pre_ext_ver)) = prerelease_id_version(self)? {
-            let new_ext_ver = if pre_ext == VERSION_RC {
+        self.increment_prerelease(VERSION_RC, DEFAULT_PRERELEASE_CHANNELS)
+    }
+
+    fn increment_prerelease(&mut self, id: &str, channels: &[&str]) -> CargoResult<()> {
+        if let Some((pre_ext, pre_ext_ver)) = prerelease_id_version(self) {
+            let rank = |ext: &str| channels.iter().position(|c| *c == ext);
+            if let (Some(current), Some(next)) = (rank(&pre_ext), rank(id)) {
+                if next < current {
+                    anyhow::bail!(
+                        "unsupported release level {id} after {pre_ext}, only later pre-releases or major, minor, and patch are supported"
+                    );
+                }
+            }
+            let new_ext_ver = if pre_ext == id {
                 pre_ext_ver.unwrap_or(0) + 1
             } else {
                 1
             };
-            self.pre = semver::Prerelease::new(&format!("{VERSION_RC}.{new_ext_ver}"))?;
+            self.pre = semver::Prerelease::new(&format!("{id}.{new_ext_ver}"))?;
             Ok(())
         } else {
             self.increment_patch();
-            self.pre = semver::Prerelease::new(&format!("{VERSION_RC}.1"))?;
+            self.pre = semver::Prerelease::new(&format!("{id}.1"))?;
             Ok(())
         }
     }
@@ -132,23 +112,66 @@ static VERSION_ALPHA: &str = "alpha";
 static VERSION_BETA: &str = "beta";
 static VERSION_RC: &str = "rc";
 
-fn prerelease_id_version(version: &semver::Version) -> CargoResult<Option<(String, Option<u64>)>> {
-    if !version.pre.is_empty() {
-        if let Some((alpha, numeric)) = version.pre.as_str().split_once('.') {
-            let alpha = alpha.to_owned();
-            let numeric = u64::from_str(numeric)
-                .map_err(|_| anyhow::format_err!("pre-release `{}` version scheme is not supported by cargo-release.  Use format like `pre`, `dev`, or `alpha.1` for prerelease", version.pre))?;
-            Ok(Some((alpha, Some(numeric))))
+/// Pre-release identifiers, from earliest to latest
+pub static DEFAULT_PRERELEASE_CHANNELS: &[&str] = &[VERSION_ALPHA, VERSION_BETA, VERSION_RC];
+
+/// Split a pre-release like `nightly.3` into its identifier and trailing number
+fn prerelease_id_version(version: &semver::Version) -> Option<(String, Option<u64>)> {
+    if version.pre.is_empty() {
+        return None;
+    }
+    let pre = version.pre.as_str();
+    match pre.rsplit_once('.') {
+        Some((id, numeric)) => match u64::from_str(numeric) {
+            Ok(numeric) => Some((id.to_owned(), Some(numeric))),
+            Err(_) => Some((pre.to_owned(), None)),
+        },
+        None => Some((pre.to_owned(), None)),
+    }
+}
+
+/// Next `YEAR.MONTH.PATCH` version as of `date` (`YYYY-MM-DD`)
+///
+/// The patch number resets when the year or month changes.
+pub fn calendar_version(current: &semver::Version, date: &str) -> CargoResult<semver::Version> {
+    let mut parts = date.splitn(3, '-').map(u64::from_str);
+    let (Some(Ok(year)), Some(Ok(month))) = (parts.next(), parts.next()) else {
+        anyhow::bail!("invalid date `{date}`, expected `YYYY-MM-DD`");
+    };
+
+    let mut version = current.clone();
+    version.build = semver::BuildMetadata::EMPTY;
+    if version.major == year && version.minor == month {
+        if version.is_prerelease() {
+            version.pre = semver::Prerelease::EMPTY;
         } else {
-            Ok(Some((version.pre.as_str().to_owned(), None)))
+            version.increment_patch();
         }
     } else {
-        Ok(None)
+        version = semver::Version::new(year, month, 0);
     }
+    Ok(version)
 }
 
 /// Upgrade an existing requirement to a new version
+///
+/// Lower bounds are raised to `version` and upper bounds are widened to include it.
 pub fn upgrade_requirement(req: &str, version: &semver::Version) -> CargoResult<Option<String>> {
+    rewrite_requirement(req, version, true)
+}
+
+/// Widen an existing requirement just enough to match a new version
+///
+/// Unlike [`upgrade_requirement`], comparators that already match `version` are left alone.
+pub fn widen_requirement(req: &str, version: &semver::Version) -> CargoResult<Option<String>> {
+    rewrite_requirement(req, version, false)
+}
+
+fn rewrite_requirement(
+    req: &str,
+    version: &semver::Version,
+    raise: bool,
+) -> CargoResult<Option<String>> {
     let req_text = req.to_owned();
     let raw_req = semver::VersionReq::parse(&req_text)
         .expect("semver to generate valid version requirements");
@@ -159,7 +182,7 @@ pub fn upgrade_requirement(req: &str, version: &semver::Version) -> CargoResult<
         let comparators: Result<Vec<_>, _> = raw_req
             .comparators
             .into_iter()
-            .map(|p| set_comparator(p, version))
+            .map(|p| set_comparator(p, version, raise))
             .collect();
         let comparators = comparators?;
         let new_req = semver::VersionReq { comparators };
@@ -167,13 +190,9 @@ pub fn upgrade_requirement(req: &str, version: &semver::Version) -> CargoResult<
         if new_req_text.starts_with('^') && !req.starts_with('^') {
             new_req_text.remove(0);
         }
-        // Validate contract
-        #[cfg(debug_assertions)]
-        {
-            assert!(
-                new_req.matches(version),
-                "Invalid req created: {new_req_text}"
-            );
+        if !new_req.matches(version) {
+            // Not every requirement can be stretched over a pre-release, like `<3`
+            anyhow::bail!("cannot rewrite `{req}` to match {version}");
         }
         if new_req_text == req_text {
             Ok(None)
@@ -186,7 +205,13 @@ pub fn upgrade_requirement(req: &str, version: &semver::Version) -> CargoResult<
 fn set_comparator(
     mut pred: semver::Comparator,
     version: &semver::Version,
+    raise: bool,
 ) -> CargoResult<semver::Comparator> {
+    if !raise && pred.matches(version) {
+        return Ok(pred);
+    }
+    let mut release = version.clone();
+    release.pre = semver::Prerelease::EMPTY;
     match pred.op {
         semver::Op::Wildcard => {
             pred.major = version.major;
@@ -199,9 +224,23 @@ fn set_comparator(
             Ok(pred)
         }
         semver::Op::Exact => Ok(assign_partial_req(version, pred)),
-        semver::Op::Greater | semver::Op::GreaterEq | semver::Op::Less | semver::Op::LessEq => Err(
-            anyhow::format_err!("support for modifying {} is currently unsupported", pred),
-        ),
+        semver::Op::Greater | semver::Op::GreaterEq => {
+            if !raise && pred.matches(&release) {
+                // Raising the lower bound to a pre-release would narrow the requirement
+                return Ok(pred);
+            }
+            pred.op = semver::Op::GreaterEq;
+            Ok(assign_partial_bound(version, pred))
+        }
+        semver::Op::Less | semver::Op::LessEq => {
+            if pred.matches(&release) {
+                Ok(pred)
+            } else if pred.op == semver::Op::LessEq {
+                Ok(assign_partial_bound(version, pred))
+            } else {
+                Ok(exclusive_upper_bound(version, pred))
+            }
+        }
         semver::Op::Tilde => Ok(assign_partial_req(version, pred)),
         semver::Op::Caret => Ok(assign_partial_req(version, pred)),
         _ => {
@@ -218,17 +257,51 @@ fn assign_partial_req(
     version: &semver::Version,
     mut pred: semver::Comparator,
 ) -> semver::Comparator {
+    // Only a full version can carry a pre-release
+    let full = !version.pre.is_empty();
     pred.major = version.major;
-    if pred.minor.is_some() {
+    if full || pred.minor.is_some() {
         pred.minor = Some(version.minor);
     }
-    if pred.patch.is_some() {
+    if full || pred.patch.is_some() {
         pred.patch = Some(version.patch);
     }
     pred.pre = version.pre.clone();
     pred
 }
 
+/// Like [`assign_partial_req`] but only keeps the pre-release on full versions
+fn assign_partial_bound(version: &semver::Version, pred: semver::Comparator) -> semver::Comparator {
+    let mut pred = assign_partial_req(version, pred);
+    if pred.patch.is_none() {
+        pred.pre = semver::Prerelease::EMPTY;
+    }
+    pred
+}
+
+/// Lowest `<` bound, at the existing precision, that includes `version`
+fn exclusive_upper_bound(
+    version: &semver::Version,
+    mut pred: semver::Comparator,
+) -> semver::Comparator {
+    match (pred.minor, pred.patch) {
+        (None, _) => {
+            pred.major = version.major + 1;
+        }
+        (Some(_), None) => {
+            pred.major = version.major;
+            pred.minor = Some(version.minor + 1);
+        }
+        (Some(_), Some(_)) => {
+            pred.major = version.major;
+            pred.minor = Some(version.minor);
+            pred.patch = Some(version.patch + 1);
+        }
+    }
+    pred.pre = semver::Prerelease::EMPTY;
+    pred
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -291,6 +364,34 @@ mod test {
             assert_eq!(v3, semver::Version::parse("1.0.1-rc.2").unwrap());
         }
 
+        #[test]
+        fn prerelease() {
+            let channels = ["dev", "alpha", "nightly", "beta", "rc"];
+
+            let mut v = semver::Version::parse("1.0.0").unwrap();
+            v.increment_prerelease("nightly", &channels).unwrap();
+            assert_eq!(v, semver::Version::parse("1.0.1-nightly.1").unwrap());
+
+            let mut v2 = semver::Version::parse("1.0.1-nightly.1").unwrap();
+            v2.increment_prerelease("nightly", &channels).unwrap();
+            assert_eq!(v2, semver::Version::parse("1.0.1-nightly.2").unwrap());
+
+            let mut v3 = semver::Version::parse("1.0.1-alpha.2").unwrap();
+            v3.increment_prerelease("nightly", &channels).unwrap();
+            assert_eq!(v3, semver::Version::parse("1.0.1-nightly.1").unwrap());
+
+            let mut v4 = semver::Version::parse("1.0.1-beta.1").unwrap();
+            assert!(v4.increment_prerelease("nightly", &channels).is_err());
+
+            let mut v5 = semver::Version::parse("1.0.1-preview.1").unwrap();
+            v5.increment_prerelease("nightly", &channels).unwrap();
+            assert_eq!(v5, semver::Version::parse("1.0.1-nightly.1").unwrap());
+
+            let mut v6 = semver::Version::parse("1.0.1-preview.build.7").unwrap();
+            v6.increment_prerelease("preview.build", &channels).unwrap();
+            assert_eq!(v6, semver::Version::parse("1.0.1-preview.build.8").unwrap());
+        }
+
         #[test]
         fn metadata() {
             let mut v = semver::Version::parse("1.0.0").unwrap();
@@ -310,6 +411,14 @@ mod test {
             assert_eq!(actual.as_deref(), expected);
         }
 
+        #[track_caller]
+        fn assert_req_widen<'a, O: Into<Option<&'a str>>>(version: &str, req: &str, expected: O) {
+            let version = semver::Version::parse(version).unwrap();
+            let actual = widen_requirement(req, &version).unwrap();
+            let expected = expected.into();
+            assert_eq!(actual.as_deref(), expected);
+        }
+
         #[test]
         fn wildcard_major() {
             assert_req_bump("1.0.0", "*", None);
@@ -417,5 +526,89 @@ mod test {
             assert_req_bump("1.1.1", "=1.0.0", "=1.1.1");
             assert_req_bump("2.0.0", "=1.0.0", "=2.0.0");
         }
+
+        #[test]
+        fn greater_eq() {
+            assert_req_bump("1.2.0", ">=1.2", None);
+            assert_req_bump("1.5.3", ">=1.2", ">=1.5");
+            assert_req_bump("2.0.0", ">1.2.3", ">=2.0.0");
+
+            assert_req_widen("1.5.3", ">=1.2", None);
+            assert_req_widen("1.5.3", ">1.5.3", ">=1.5.3");
+        }
+
+        #[test]
+        fn less() {
+            assert_req_bump("2.5.0", "<3", None);
+            assert_req_bump("3.1.0", "<3", "<4");
+            assert_req_bump("1.5.0", "<1.5", "<1.6");
+            assert_req_bump("1.5.2", "<1.5.2", "<1.5.3");
+            assert_req_bump("3.1.0", "<=3.0", "<=3.1");
+        }
+
+        #[test]
+        fn range() {
+            assert_req_bump("2.0.0", ">=1.2, <3", ">=2.0, <3");
+            assert_req_bump("3.1.0", ">=1.2, <3", ">=3.1, <4");
+            assert_req_bump("3.1.0", "^1.2, <3", "^3.1, <4");
+
+            assert_req_widen("3.1.0", ">=1.2, <3", ">=1.2, <4");
+            assert_req_widen("2.0.0", ">=1.2, <3", None);
+            assert_req_widen("3.1.0", "^1.2, <3", "^3.1, <4");
+        }
+
+        #[test]
+        fn range_prerelease() {
+            let version = semver::Version::parse("3.0.0-alpha.1").unwrap();
+            assert!(widen_requirement(">=1.2, <3", &version).is_err());
+        }
+
+        #[test]
+        fn prerelease() {
+            assert_req_bump("3.0.0-alpha.1", "1.2", "3.0.0-alpha.1");
+            assert_req_bump("3.0.0-alpha.1", "^1.2", "^3.0.0-alpha.1");
+            assert_req_bump("3.0.0-alpha.1", ">=1.2", ">=3.0.0-alpha.1");
+            assert_req_widen("3.0.0-alpha.1", "^1.2", "^3.0.0-alpha.1");
+
+            let version = semver::Version::parse("3.0.0-alpha.1").unwrap();
+            assert!(upgrade_requirement("<3", &version).is_err());
+            assert!(widen_requirement("<3", &version).is_err());
+            assert!(widen_requirement(">=1.2", &version).is_err());
+            assert!(upgrade_requirement("1.*", &version).is_err());
+        }
+    }
+
+    mod calendar_version {
+        use super::*;
+
+        fn assert_calendar(current: &str, date: &str, expected: &str) {
+            let current = semver::Version::parse(current).unwrap();
+            let actual = calendar_version(&current, date).unwrap();
+            assert_eq!(actual.to_string(), expected);
+        }
+
+        #[test]
+        fn same_month() {
+            assert_calendar("2024.3.0", "2024-03-15", "2024.3.1");
+            assert_calendar("2024.3.4+build.1", "2024-03-15", "2024.3.5");
+            assert_calendar("2024.3.5-rc.1", "2024-03-15", "2024.3.5");
+        }
+
+        #[test]
+        fn new_month() {
+            assert_calendar("2024.3.4", "2024-04-01", "2024.4.0");
+            assert_calendar("2024.12.4", "2025-01-01", "2025.1.0");
+        }
+
+        #[test]
+        fn from_semver() {
+            assert_calendar("0.1.0", "2024-04-01", "2024.4.0");
+        }
+
+        #[test]
+        fn invalid_date() {
+            let current = semver::Version::parse("2024.3.0").unwrap();
+            assert!(calendar_version(&current, "today").is_err());
+        }
     }
 }
@@ -0,0 +1,332 @@
+use std::ops::Range;
+use std::path::Path;
+
+use crate::config::VersionFile;
+use crate::error::CargoResult;
+
+/// Set the version in each of `version_files`, leaving the rest of each file as-is
+pub fn update_version_files(
+    version_files: &[VersionFile],
+    version: &str,
+    cwd: &Path,
+    dry_run: bool,
+) -> CargoResult<()> {
+    for version_file in version_files {
+        let path = &version_file.file;
+        let file = cwd.join(path);
+        if !file.exists() {
+            anyhow::bail!("unable to find file {} to set the version", file.display());
+        }
+        let data = std::fs::read_to_string(&file)?;
+        let updated = set_version(&data, path, &version_file.path, version)?;
+
+        if data != updated {
+            if dry_run {
+                let _ = crate::ops::shell::status(
+                    "Updating",
+                    format!(
+                        "{} in {}\n{}",
+                        version_file.path,
+                        path.display(),
+                        crate::ops::diff::unified_diff(&data, &updated, path, "updated")
+                    ),
+                );
+            } else {
+                std::fs::write(&file, updated)?;
+            }
+        } else {
+            log::trace!("{} is unchanged", file.display());
+        }
+    }
+    Ok(())
+}
+
+/// Set the string at the dot-separated `key_path`, picking the format from `path`'s extension
+pub fn set_version(data: &str, path: &Path, key_path: &str, version: &str) -> CargoResult<String> {
+    let keys = key_path.split('.').collect::<Vec<_>>();
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let span = match extension {
+        "toml" => return set_toml(data, &keys, version),
+        "json" => json_string_span(data, &keys),
+        "yaml" | "yml" => yaml_scalar_span(data, &keys),
+        _ => anyhow::bail!(
+            "unsupported version file {}, expected `.toml`, `.json`, or `.yaml`",
+            path.display()
+        ),
+    };
+    let span =
+        span.map_err(|err| anyhow::format_err!("failed to parse {}: {err}", path.display()))?;
+    let Some(span) = span else {
+        anyhow::bail!("no string `{key_path}` found in {}", path.display());
+    };
+    let mut updated = data.to_owned();
+    updated.replace_range(span, version);
+    Ok(updated)
+}
+
+fn set_toml(data: &str, keys: &[&str], version: &str) -> CargoResult<String> {
+    let mut doc: toml_edit::DocumentMut = data.parse()?;
+    let mut item = doc.as_item_mut();
+    for key in keys {
+        item = item
+            .as_table_like_mut()
+            .and_then(|t| t.get_mut(key))
+            .ok_or_else(|| anyhow::format_err!("no `{}` found", keys.join(".")))?;
+    }
+    let Some(value) = item.as_value_mut().filter(|v| v.is_str()) else {
+        anyhow::bail!("`{}` is not a string", keys.join("."));
+    };
+    let decor = value.decor().clone();
+    *value = toml_edit::Value::from(version);
+    *value.decor_mut() = decor;
+    Ok(doc.to_string())
+}
+
+/// Byte range of the contents of the JSON string at `keys`
+fn json_string_span(data: &str, keys: &[&str]) -> CargoResult<Option<Range<usize>>> {
+    type Object<'a> = std::collections::HashMap<String, &'a serde_json::value::RawValue>;
+
+    // `RawValue`s borrow from `data`, so each one's position in it is its span
+    let mut value: &serde_json::value::RawValue = serde_json::from_str(data)?;
+    for key in keys {
+        let Ok(mut object) = serde_json::from_str::<Object<'_>>(value.get()) else {
+            return Ok(None);
+        };
+        let Some(child) = object.remove(*key) else {
+            return Ok(None);
+        };
+        value = child;
+    }
+    let raw = value.get();
+    if serde_json::from_str::<String>(raw).is_err() {
+        return Ok(None);
+    }
+    let start = raw.as_ptr() as usize - data.as_ptr() as usize;
+    Ok(Some(start + 1..start + raw.len() - 1))
+}
+
+/// Byte range of the scalar at `keys` in a YAML block mapping
+///
+/// Only a single document of block mappings with plain or quoted scalars is supported.
+/// Quotes around the scalar are kept, as are trailing comments.
+fn yaml_scalar_span(data: &str, keys: &[&str]) -> CargoResult<Option<Range<usize>>> {
+    let mut documents = data
+        .lines()
+        .filter(|l| !l.trim_start().is_empty() && !l.trim_start().starts_with('#'))
+        .skip_while(|l| l.starts_with("---"));
+    if documents.any(|l| l.starts_with("---") || l.starts_with("...")) {
+        anyhow::bail!("multiple YAML documents are unsupported");
+    }
+
+    let mut depth = 0;
+    let mut parent_indent = None;
+    let mut child_indent = None;
+    let mut has_content = false;
+    let mut offset = 0;
+    for line in data.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let content = line.trim_end_matches(['\r', '\n']);
+        let trimmed = content.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if content.starts_with("---") {
+            continue;
+        }
+        if !has_content && trimmed.starts_with(['{', '[']) {
+            anyhow::bail!("YAML flow collections are unsupported");
+        }
+        has_content = true;
+        let indent = content.len() - trimmed.len();
+        if parent_indent.is_some_and(|p| indent <= p) {
+            return Ok(None);
+        }
+        if *child_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+
+        let key = keys[depth];
+        let rest = [key.to_owned(), format!("\"{key}\""), format!("'{key}'")]
+            .iter()
+            .find_map(|k| trimmed.strip_prefix(k.as_str())?.strip_prefix(':'));
+        let Some(rest) = rest else {
+            continue;
+        };
+        let rest_start = line_start + (rest.as_ptr() as usize - line.as_ptr() as usize);
+        let value = rest.trim_start();
+        let value_start = rest_start + (rest.len() - value.len());
+        if let Some(unsupported) = unsupported_yaml(value) {
+            anyhow::bail!(
+                "{unsupported} at `{}` are unsupported",
+                keys[..=depth].join(".")
+            );
+        }
+        if depth + 1 < keys.len() {
+            if !(value.is_empty() || value.starts_with('#')) {
+                return Ok(None);
+            }
+            depth += 1;
+            parent_indent = Some(indent);
+            child_indent = None;
+            continue;
+        }
+
+        let span = yaml_scalar(value).map(|s| value_start + s.start..value_start + s.end);
+        return Ok(span);
+    }
+    Ok(None)
+}
+
+/// Describe the YAML features `value` starts with that are not supported
+fn unsupported_yaml(value: &str) -> Option<&'static str> {
+    match value.chars().next()? {
+        '{' | '[' => Some("YAML flow collections"),
+        '&' | '*' => Some("YAML anchors and aliases"),
+        '!' => Some("YAML tags"),
+        '|' | '>' => Some("YAML block scalars"),
+        _ => None,
+    }
+}
+
+/// Byte range of the contents of the scalar at the start of `value`, excluding quotes
+fn yaml_scalar(value: &str) -> Option<Range<usize>> {
+    let (contents, rest) = match value.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let mut escaped = false;
+            let end = value.char_indices().skip(1).find_map(|(i, c)| {
+                match c {
+                    '\\' if quote == '"' && !escaped => escaped = true,
+                    c if c == quote && !escaped => return Some(i),
+                    _ => escaped = false,
+                }
+                None
+            })?;
+            (1..end, &value[end + 1..])
+        }
+        '#' => return None,
+        _ => {
+            let end = value.find(" #").unwrap_or(value.len());
+            let end = value[..end].trim_end().len();
+            (0..end, &value[end..])
+        }
+    };
+    let rest = rest.trim_start();
+    if !(rest.is_empty() || rest.starts_with('#')) || contents.is_empty() {
+        return None;
+    }
+    Some(contents)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod set_version {
+        use super::*;
+
+        #[test]
+        fn toml() {
+            let data = r#"[project]
+name = "foo"
+version = "0.1.0"  # keep me
+"#;
+            let actual = set_version(
+                data,
+                Path::new("pyproject.toml"),
+                "project.version",
+                "0.2.0",
+            )
+            .unwrap();
+            assert_eq!(
+                actual,
+                r#"[project]
+name = "foo"
+version = "0.2.0"  # keep me
+"#
+            );
+        }
+
+        #[test]
+        fn json() {
+            let data = r#"{
+  "name": "foo",
+  "scripts": { "version": "echo \"1.0\"" },
+  "files": ["a", {"version": "x"}],
+  "private": true,
+  "version": "0.1.0",
+  "engines": {
+    "node": ">=18"
+  }
+}
+"#;
+            let actual = set_version(data, Path::new("package.json"), "version", "0.2.0").unwrap();
+            assert_eq!(actual, data.replace("\"0.1.0\"", "\"0.2.0\""));
+
+            let actual =
+                set_version(data, Path::new("package.json"), "engines.node", "20").unwrap();
+            assert_eq!(actual, data.replace(">=18", "20"));
+
+            assert!(set_version(data, Path::new("package.json"), "private", "1").is_err());
+            assert!(set_version(data, Path::new("package.json"), "missing", "1").is_err());
+            assert!(
+                set_version("{\"version\": ", Path::new("package.json"), "version", "1").is_err()
+            );
+        }
+
+        #[test]
+        fn yaml() {
+            let data = r#"name: foo
+# version: 0.0.0
+dependencies:
+  version: 9.9.9
+package:
+  name: foo
+  version: "0.1.0" # keep me
+version: 0.1.0
+"#;
+            let actual =
+                set_version(data, Path::new("recipe.yaml"), "package.version", "0.2.0").unwrap();
+            assert_eq!(actual, data.replace("\"0.1.0\"", "\"0.2.0\""));
+
+            let actual = set_version(data, Path::new("recipe.yml"), "version", "0.2.0").unwrap();
+            assert_eq!(actual, data.replace("version: 0.1.0", "version: 0.2.0"));
+
+            assert!(set_version(data, Path::new("recipe.yml"), "package.missing", "1").is_err());
+        }
+
+        #[test]
+        fn yaml_quoted() {
+            let data = "version: \"0.1.0 #1\" # keep me\nname: 'foo'\n";
+            let actual = set_version(data, Path::new("a.yaml"), "version", "0.2.0").unwrap();
+            assert_eq!(actual, "version: \"0.2.0\" # keep me\nname: 'foo'\n");
+
+            let data = "description: |\n  {\n  version: 9.9.9\nversion: 0.1.0\n";
+            let actual = set_version(data, Path::new("a.yaml"), "version", "0.2.0").unwrap();
+            assert_eq!(actual, data.replace("0.1.0", "0.2.0"));
+        }
+
+        #[test]
+        fn yaml_unsupported() {
+            for data in [
+                "package: {version: 0.1.0}\n",
+                "{package: {version: 0.1.0}}\n",
+                "package:\n  version: &v 0.1.0\n",
+                "package:\n  version: *v\n",
+                "package:\n  version: !!str 0.1.0\n",
+                "package:\n  version: |\n    0.1.0\n",
+                "package:\n  version: 0.1.0\n---\npackage:\n  version: 0.1.0\n",
+            ] {
+                let actual = set_version(data, Path::new("a.yaml"), "package.version", "0.2.0");
+                let err = actual.expect_err(data).to_string();
+                assert!(err.contains("unsupported"), "{data}: {err}");
+            }
+        }
+
+        #[test]
+        fn unsupported() {
+            assert!(set_version("", Path::new("setup.py"), "version", "1").is_err());
+        }
+    }
+}
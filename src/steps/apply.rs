@@ -0,0 +1,60 @@
+use crate::config;
+use crate::error::CliError;
+
+/// Release a plan saved with `--plan-format toml`
+///
+/// Fails if `HEAD` is not based on the planned commit or if a planned manifest changed
+#[derive(Debug, Clone, clap::Args)]
+pub struct ApplyStep {
+    /// Plan file to release
+    #[arg(value_name = "PLAN")]
+    plan: std::path::PathBuf,
+
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    /// Actually perform a release. Dry-run mode is the default
+    #[arg(short = 'x', long)]
+    execute: bool,
+
+    /// Skip release confirmation and version preview
+    #[arg(long)]
+    no_confirm: bool,
+
+    #[command(flatten)]
+    config: config::ConfigArgs,
+}
+
+impl ApplyStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        // The plan records these per package, so the flags would be silently overwritten
+        let publish = self.config.publish.to_config();
+        let overridden = [
+            ("--[no-]publish", publish.publish.is_some()),
+            ("--registry", publish.registry.is_some()),
+            ("--features", publish.enable_features.is_some()),
+            ("--all-features", publish.enable_all_features.is_some()),
+            ("--[no-]tag", self.config.tag.to_config().tag.is_some()),
+            ("--[no-]push", self.config.push.to_config().push.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(flag, set)| set.then_some(flag))
+        .collect::<Vec<_>>();
+        if !overridden.is_empty() {
+            let _ = crate::ops::shell::error(format!(
+                "{} cannot be used with `cargo release apply`, the plan records these settings",
+                overridden.join(", ")
+            ));
+            return Err(2.into());
+        }
+
+        super::release::ReleaseStep::from_plan(
+            self.plan.clone(),
+            self.manifest.clone(),
+            self.config.clone(),
+            self.execute,
+            self.no_confirm,
+        )
+        .run()
+    }
+}
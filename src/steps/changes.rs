@@ -1,7 +1,11 @@
 use crate::error::CargoResult;
 use crate::error::CliError;
+use crate::ops::changelog::Section;
 use crate::ops::git;
+use crate::ops::manifest::ManifestApi;
+use crate::ops::replace::NOW;
 use crate::ops::version::VersionExt as _;
+use crate::steps::BumpLevel;
 use crate::steps::plan;
 use clap_cargo::style::{ERROR, GOOD, NOP, WARN};
 
@@ -30,6 +34,10 @@ pub struct ChangesStep {
     /// The name of tag for the previous release.
     #[arg(long, value_name = "NAME", help_heading = "Version")]
     prev_tag_name: Option<String>,
+
+    /// Format for reporting changes; `json` and `markdown` are written to stdout
+    #[arg(long, value_enum, default_value_t)]
+    format: ChangesFormat,
 }
 
 impl ChangesStep {
@@ -89,7 +97,7 @@ impl ChangesStep {
             log::Level::Warn,
         )?;
 
-        changes(&ws_meta, &selected_pkgs)?;
+        changes(&ws_meta, &selected_pkgs, self.format)?;
 
         super::finish(failed, dry_run)
     }
@@ -104,175 +112,496 @@ impl ChangesStep {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ChangesFormat {
+    /// Annotated commit summaries for a terminal
+    #[default]
+    Text,
+    /// Per-package commits and suggested bump level
+    Json,
+    /// Changelog-style sections per package
+    Markdown,
+}
+
 pub fn changes(
     ws_meta: &cargo_metadata::Metadata,
     selected_pkgs: &[plan::PackageRelease],
+    format: ChangesFormat,
 ) -> CargoResult<()> {
+    let mut reports = Vec::new();
     for pkg in selected_pkgs {
         let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
         let crate_name = pkg.meta.name.as_str();
-        if let Some(prior_tag_name) = &pkg.prior_tag {
-            let workspace_root = ws_meta.workspace_root.as_std_path();
-            let repo = git2::Repository::discover(workspace_root)?;
-
-            let mut tag_id = None;
-            let fq_prior_tag_name = format!("refs/tags/{prior_tag_name}");
-            repo.tag_foreach(|id, name| {
-                if name == fq_prior_tag_name.as_bytes() {
-                    tag_id = Some(id);
-                    false
-                } else {
-                    true
-                }
-            })?;
-            let tag_id = tag_id
-                .ok_or_else(|| anyhow::format_err!("could not find tag {}", prior_tag_name))?;
-
-            let head_id = repo.head()?.peel_to_commit()?.id();
-
-            let mut revwalk = repo.revwalk()?;
-            revwalk.push_range(&format!("{tag_id}..{head_id}"))?;
-
-            let mut commits = Vec::new();
-            for commit_id in revwalk {
-                let commit_id = commit_id?;
-                let commit = repo.find_commit(commit_id)?;
-                if 1 < commit.parent_count() {
-                    // Assuming merge commits can be ignored
-                    continue;
-                }
-                let parent_tree = commit.parent(0).ok().map(|c| c.tree()).transpose()?;
-                let tree = commit.tree()?;
-                let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
-
-                let mut changed_paths = std::collections::BTreeSet::new();
-                for delta in diff.deltas() {
-                    let old_path = delta.old_file().path();
-                    let new_path = delta.new_file().path();
-                    for entry_relpath in [old_path, new_path].into_iter().flatten() {
-                        for path in pkg
-                            .package_content
-                            .iter()
-                            .filter_map(|p| p.strip_prefix(workspace_root).ok())
-                        {
-                            if path == entry_relpath {
-                                changed_paths.insert(path.to_owned());
-                            }
-                        }
-                    }
-                }
+        let Some(prior_tag_name) = &pkg.prior_tag else {
+            log::debug!(
+                "Cannot detect changes for {} because no tag was found. Try setting `--prev-tag-name <TAG>`.",
+                crate_name,
+            );
+            reports.push(PackageChanges {
+                name: crate_name.to_owned(),
+                prior_tag: None,
+                version: version.full_version_string.clone(),
+                commits: Vec::new(),
+                manifest_changes: Vec::new(),
+                contributors: Vec::new(),
+                suggested_bump: None,
+            });
+            continue;
+        };
+
+        let workspace_root = ws_meta.workspace_root.as_std_path();
+        let commits = collect_commits(ws_meta, pkg, prior_tag_name)?;
+        let manifest_changes = manifest_changes(ws_meta, pkg, prior_tag_name)?.breaking;
+
+        let max_status = max_status(&commits, &manifest_changes, &version.full_version);
+        let unbumped = pkg
+            .planned_tag
+            .as_deref()
+            .and_then(|t| git::tag_exists(workspace_root, t).ok())
+            .unwrap_or(false);
+        let suggested = max_status.and_then(|s| suggest_bump(&version.full_version, s, !unbumped));
+
+        if format == ChangesFormat::Text && !(commits.is_empty() && manifest_changes.is_empty()) {
+            crate::ops::shell::status(
+                "Changes",
+                format!(
+                    "for {} from {} to {}",
+                    crate_name, prior_tag_name, version.full_version_string
+                ),
+            )?;
+            let prefix = format!("{:>13}", " ");
+            for commit in &commits {
+                #[allow(clippy::needless_borrow)] // False positive
+                let _ = crate::ops::shell::write_stderr(&prefix, &NOP);
+                let _ = crate::ops::shell::write_stderr(&commit.short_id, &WARN);
+                let _ = crate::ops::shell::write_stderr(" ", &NOP);
+                let _ = crate::ops::shell::write_stderr(commit.title(), &NOP);
+                write_status(commit.status());
+                let _ = crate::ops::shell::write_stderr("\n", &NOP);
+            }
+            for change in &manifest_changes {
+                let _ = crate::ops::shell::write_stderr(&prefix, &NOP);
+                let _ = crate::ops::shell::write_stderr("Cargo.toml", &WARN);
+                let _ = crate::ops::shell::write_stderr(" ", &NOP);
+                let _ = crate::ops::shell::write_stderr(change, &NOP);
+                write_status(Some(CommitStatus::Breaking));
+                let _ = crate::ops::shell::write_stderr("\n", &NOP);
+            }
+            if let Some(suggested) = suggested {
+                let _ = crate::ops::shell::note(format!(
+                    "to update the version, run `cargo release version -p {crate_name} {suggested}`"
+                ));
+            } else if max_status.is_some() && unbumped {
+                let _ = crate::ops::shell::note(format!(
+                    "to update the version, run `cargo release version -p {crate_name} <LEVEL|VERSION>`"
+                ));
+            }
+        }
+
+        reports.push(PackageChanges {
+            name: crate_name.to_owned(),
+            prior_tag: Some(prior_tag_name.clone()),
+            version: version.full_version_string.clone(),
+            contributors: contributors(&commits),
+            commits,
+            manifest_changes,
+            suggested_bump: suggested,
+        });
+    }
 
-                if !changed_paths.is_empty() {
-                    let short_id =
-                        String::from_utf8_lossy(&repo.find_object(commit_id, None)?.short_id()?)
-                            .into_owned();
-                    commits.push(PackageCommit {
-                        id: commit_id,
-                        short_id,
-                        summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or(b""))
-                            .into_owned(),
-                        message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
-                        paths: changed_paths,
-                    });
+    let output = match format {
+        ChangesFormat::Text => return Ok(()),
+        ChangesFormat::Json => {
+            let mut output = serde_json::to_string_pretty(&reports)?;
+            output.push('\n');
+            output
+        }
+        ChangesFormat::Markdown => {
+            let mut output = String::new();
+            for report in reports
+                .iter()
+                .filter(|r| !(r.commits.is_empty() && r.manifest_changes.is_empty()))
+            {
+                if !output.is_empty() {
+                    output.push('\n');
                 }
+                output.push_str(&format!(
+                    "## {} {} (since {})\n",
+                    report.name,
+                    report.version,
+                    report.prior_tag.as_deref().unwrap_or_default()
+                ));
+                output.push_str(&crate::ops::changelog::render_sections(&changelog_entries(
+                    &report.commits,
+                    &report.manifest_changes,
+                )));
             }
+            output
+        }
+    };
+    std::io::Write::write_all(&mut std::io::stdout(), output.as_bytes())?;
 
-            if !commits.is_empty() {
-                crate::ops::shell::status(
-                    "Changes",
-                    format!(
-                        "for {} from {} to {}",
-                        crate_name, prior_tag_name, version.full_version_string
-                    ),
-                )?;
-                let prefix = format!("{:>13}", " ");
-                let mut max_status = None;
-                for commit in &commits {
-                    #[allow(clippy::needless_borrow)] // False positive
-                    let _ = crate::ops::shell::write_stderr(&prefix, &NOP);
-                    let _ = crate::ops::shell::write_stderr(&commit.short_id, &WARN);
-                    let _ = crate::ops::shell::write_stderr(" ", &NOP);
-                    let _ = crate::ops::shell::write_stderr(&commit.summary, &NOP);
-
-                    let current_status = commit.status();
-                    write_status(current_status);
-                    let _ = crate::ops::shell::write_stderr("\n", &NOP);
-                    match (current_status, max_status) {
-                        (Some(cur), Some(max)) => {
-                            max_status = Some(cur.max(max));
-                        }
-                        (Some(s), None) | (None, Some(s)) => {
-                            max_status = Some(s);
-                        }
-                        (None, None) => {}
-                    }
-                }
-                if version.full_version.is_prerelease() {
-                    // Enough unknowns about pre-release to not bother
-                    max_status = None;
-                }
-                let unbumped = pkg
-                    .planned_tag
-                    .as_deref()
-                    .and_then(|t| git::tag_exists(workspace_root, t).ok())
-                    .unwrap_or(false);
-                let bumped = !unbumped;
-                if let Some(max_status) = max_status {
-                    let suggested = match max_status {
-                        CommitStatus::Breaking => {
-                            match (
-                                version.full_version.major,
-                                version.full_version.minor,
-                                version.full_version.patch,
-                            ) {
-                                (0, 0, _) if bumped => None,
-                                (0, 0, _) => Some("patch"),
-                                (0, _, 0) if bumped => None,
-                                (0, _, _) => Some("minor"),
-                                (_, 0, 0) if bumped => None,
-                                (_, _, _) => Some("major"),
-                            }
-                        }
-                        CommitStatus::Feature => {
-                            match (
-                                version.full_version.major,
-                                version.full_version.minor,
-                                version.full_version.patch,
-                            ) {
-                                (0, 0, _) if bumped => None,
-                                (0, 0, _) => Some("patch"),
-                                (0, _, _) if bumped => None,
-                                (0, _, _) => Some("patch"),
-                                (_, _, 0) if bumped => None,
-                                (_, _, _) => Some("minor"),
-                            }
-                        }
-                        CommitStatus::Fix if bumped => None,
-                        CommitStatus::Fix => Some("patch"),
-                        CommitStatus::Ignore => None,
-                    };
-                    if let Some(suggested) = suggested {
-                        let _ = crate::ops::shell::note(format!(
-                            "to update the version, run `cargo release version -p {crate_name} {suggested}`"
-                        ));
-                    } else if unbumped {
-                        let _ = crate::ops::shell::note(format!(
-                            "to update the version, run `cargo release version -p {crate_name} <LEVEL|VERSION>`"
-                        ));
-                    }
+    Ok(())
+}
+
+/// The level that `commits` call for, relative to `version`
+///
+/// `bumped` is whether `version` is unreleased, and so may already cover the changes.
+pub fn suggest_level(
+    commits: &[PackageCommit],
+    manifest_changes: &[String],
+    version: &semver::Version,
+    bumped: bool,
+) -> Option<BumpLevel> {
+    max_status(commits, manifest_changes, version).and_then(|s| suggest_bump(version, s, bumped))
+}
+
+fn max_status(
+    commits: &[PackageCommit],
+    manifest_changes: &[String],
+    version: &semver::Version,
+) -> Option<CommitStatus> {
+    if version.is_prerelease() {
+        // Enough unknowns about pre-release to not bother
+        return None;
+    }
+    if !manifest_changes.is_empty() {
+        return Some(CommitStatus::Breaking);
+    }
+    commits.iter().filter_map(|c| c.status()).max()
+}
+
+/// The smallest level that covers `max_status`, if `version` doesn't already
+fn suggest_bump(
+    version: &semver::Version,
+    max_status: CommitStatus,
+    bumped: bool,
+) -> Option<BumpLevel> {
+    match max_status {
+        CommitStatus::Breaking => match (version.major, version.minor, version.patch) {
+            (0, 0, _) if bumped => None,
+            (0, 0, _) => Some(BumpLevel::Patch),
+            (0, _, 0) if bumped => None,
+            (0, _, _) => Some(BumpLevel::Minor),
+            (_, 0, 0) if bumped => None,
+            (_, _, _) => Some(BumpLevel::Major),
+        },
+        CommitStatus::Feature => match (version.major, version.minor, version.patch) {
+            (0, 0, _) if bumped => None,
+            (0, 0, _) => Some(BumpLevel::Patch),
+            (0, _, _) if bumped => None,
+            (0, _, _) => Some(BumpLevel::Patch),
+            (_, _, 0) if bumped => None,
+            (_, _, _) => Some(BumpLevel::Minor),
+        },
+        CommitStatus::Fix if bumped => None,
+        CommitStatus::Fix => Some(BumpLevel::Patch),
+        CommitStatus::Ignore => None,
+    }
+}
+
+/// Walk the commits since `since_ref`, per the package's `commit-walk` and commit filters
+pub fn walk_commits(
+    dir: &std::path::Path,
+    pkg: &plan::PackageRelease,
+    since_ref: &str,
+) -> CargoResult<Vec<git::WalkedCommit>> {
+    let filter = CommitFilter::new(&pkg.config)?;
+    let mut commits = git::walk_commits(dir, since_ref, pkg.config.commit_walk())?;
+    commits.retain(|c| {
+        let ignored = filter.is_ignored(c);
+        if ignored {
+            log::debug!("ignoring {} for {}", c.short_id, pkg.meta.name);
+        }
+        !ignored
+    });
+    Ok(commits)
+}
+
+/// `changes-ignore-authors` and `changes-ignore-messages`
+pub struct CommitFilter {
+    authors: Option<globset::GlobSet>,
+    messages: Vec<regex::Regex>,
+}
+
+impl CommitFilter {
+    pub fn new(config: &crate::config::Config) -> CargoResult<Self> {
+        let authors = if config.changes_ignore_authors().is_empty() {
+            None
+        } else {
+            let mut builder = globset::GlobSetBuilder::new();
+            for glob in config.changes_ignore_authors() {
+                builder.add(
+                    globset::GlobBuilder::new(glob)
+                        .case_insensitive(true)
+                        .backslash_escape(true)
+                        .build()?,
+                );
+            }
+            Some(builder.build()?)
+        };
+        let messages = config
+            .changes_ignore_messages()
+            .iter()
+            .map(|r| regex::Regex::new(r))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { authors, messages })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.authors.is_none() && self.messages.is_empty()
+    }
+
+    pub fn is_ignored(&self, commit: &git::WalkedCommit) -> bool {
+        self.authors
+            .as_ref()
+            .map(|a| a.is_match(&commit.author_email))
+            .unwrap_or(false)
+            || self.messages.iter().any(|r| r.is_match(&commit.message))
+    }
+}
+
+/// Find the commits since `prior_tag_name` that touch the package's content
+pub fn collect_commits(
+    ws_meta: &cargo_metadata::Metadata,
+    pkg: &plan::PackageRelease,
+    prior_tag_name: &str,
+) -> CargoResult<Vec<PackageCommit>> {
+    let workspace_root = ws_meta.workspace_root.as_std_path();
+    let walked = walk_commits(workspace_root, pkg, prior_tag_name)?;
+
+    let mut commits = Vec::new();
+    for commit in walked {
+        let mut changed_paths = std::collections::BTreeSet::new();
+        for entry_relpath in &commit.paths {
+            for path in pkg
+                .package_content
+                .iter()
+                .filter_map(|p| p.strip_prefix(workspace_root).ok())
+            {
+                if path == entry_relpath {
+                    changed_paths.insert(path.to_owned());
                 }
             }
-        } else {
-            log::debug!(
-                "Cannot detect changes for {} because no tag was found. Try setting `--prev-tag-name <TAG>`.",
-                crate_name,
-            );
+        }
+
+        if !changed_paths.is_empty() {
+            let trailers = Trailers::parse(&commit.message);
+            if trailers.skip {
+                log::debug!("skipping {} due to `Changelog: skip`", commit.short_id);
+                continue;
+            }
+            let status = trailers
+                .status
+                .or_else(|| commit_status(&commit.message, &pkg.config));
+            let references = parse_references(&commit.message, pkg.config.issue_url());
+            commits.push(PackageCommit {
+                id: commit.id,
+                short_id: commit.short_id,
+                summary: commit.summary,
+                message: commit.message,
+                note: trailers.note,
+                author: commit.author_name,
+                co_authors: trailers.co_authors,
+                references,
+                paths: changed_paths,
+                status,
+            });
+        }
+    }
+
+    Ok(commits)
+}
+
+/// Diff the package's manifest at `prior_tag_name` against the working tree
+pub fn manifest_changes(
+    ws_meta: &cargo_metadata::Metadata,
+    pkg: &plan::PackageRelease,
+    prior_tag_name: &str,
+) -> CargoResult<ManifestChanges> {
+    let workspace_root = ws_meta.workspace_root.as_std_path();
+    let ws_manifest_path = workspace_root.join("Cargo.toml");
+
+    let Some(prior_manifest) = git::show_file(workspace_root, prior_tag_name, &pkg.manifest_path)?
+    else {
+        log::debug!(
+            "{} is new since {prior_tag_name}",
+            pkg.manifest_path.display()
+        );
+        return Ok(ManifestChanges::default());
+    };
+    let prior_ws_manifest = git::show_file(workspace_root, prior_tag_name, &ws_manifest_path)?;
+    let prior = ManifestApi::parse(&prior_manifest, prior_ws_manifest.as_deref())?;
+
+    let manifest = std::fs::read_to_string(&pkg.manifest_path)?;
+    let ws_manifest = std::fs::read_to_string(&ws_manifest_path).ok();
+    let current = ManifestApi::parse(&manifest, ws_manifest.as_deref())?;
+
+    Ok(ManifestChanges {
+        breaking: crate::ops::manifest::breaking_changes(
+            &prior,
+            &current,
+            pkg.config.public_dependencies(),
+        ),
+        prior_version: prior.version,
+    })
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ManifestChanges {
+    /// The version released at the prior tag
+    pub prior_version: Option<semver::Version>,
+    pub breaking: Vec<String>,
+}
+
+/// Add a section for the planned version to the package's changelog
+pub fn update_changelog(
+    ws_meta: &cargo_metadata::Metadata,
+    pkg: &plan::PackageRelease,
+    dry_run: bool,
+) -> CargoResult<()> {
+    if !pkg.config.changelog_update() {
+        return Ok(());
+    }
+    let crate_name = pkg.meta.name.as_str();
+    let Some(version) = pkg.planned_version.as_ref() else {
+        log::debug!("not updating changelog for {crate_name}, version is unchanged");
+        return Ok(());
+    };
+
+    let (commits, manifest_changes) = if let Some(prior_tag_name) = &pkg.prior_tag {
+        (
+            collect_commits(ws_meta, pkg, prior_tag_name)?,
+            manifest_changes(ws_meta, pkg, prior_tag_name)?.breaking,
+        )
+    } else {
+        log::debug!("no prior tag for {crate_name}, adding a changelog section without entries");
+        (Vec::new(), Vec::new())
+    };
+    let release = crate::ops::changelog::render_release(
+        version.bare_version_string.as_str(),
+        NOW.as_str(),
+        &changelog_entries(&commits, &manifest_changes),
+        &contributors(&commits),
+    );
+
+    let relpath = pkg.config.changelog_path();
+    let path = pkg.package_root.join(relpath);
+    let exists = path.exists();
+    let original = if exists {
+        std::fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+    let updated = crate::ops::changelog::insert_release(&original, &release);
+    if dry_run {
+        let _ = crate::ops::shell::status(
+            "Updating",
+            format!(
+                "{}\n{}",
+                relpath.display(),
+                crate::ops::diff::unified_diff(&original, &updated, relpath, "updated")
+            ),
+        );
+    } else {
+        std::fs::write(&path, updated)?;
+        if !exists {
+            // Untracked files are not picked up by the release commit
+            git::add(&pkg.package_root, relpath, dry_run)?;
         }
     }
 
     Ok(())
 }
 
+fn changelog_entries(
+    commits: &[PackageCommit],
+    manifest_changes: &[String],
+) -> Vec<(Section, String)> {
+    let manifest_entries = manifest_changes
+        .iter()
+        .map(|c| (Section::Breaking, format!("Manifest: {c}")));
+    let commit_entries = commits.iter().filter_map(|c| {
+        let section = match c.status() {
+            Some(CommitStatus::Breaking) => Section::Breaking,
+            Some(CommitStatus::Feature) => Section::Added,
+            Some(CommitStatus::Fix) => Section::Fixed,
+            Some(CommitStatus::Ignore) => return None,
+            None => Section::Changed,
+        };
+        Some((section, linkify(c.description(), &c.references)))
+    });
+    manifest_entries.chain(commit_entries).collect()
+}
+
+/// Unique authors and co-authors of `commits`, sorted by name
+pub fn contributors(commits: &[PackageCommit]) -> Vec<String> {
+    let contributors = commits
+        .iter()
+        .flat_map(|c| std::iter::once(&c.author).chain(c.co_authors.iter()))
+        .filter(|n| !n.is_empty())
+        .cloned()
+        .collect::<std::collections::BTreeSet<_>>();
+    contributors.into_iter().collect()
+}
+
+/// Unique issue and pull request references in `commits`
+pub fn references(commits: &[PackageCommit]) -> Vec<Reference> {
+    let mut references: Vec<Reference> = Vec::new();
+    for reference in commits.iter().flat_map(|c| c.references.iter()) {
+        if !references.iter().any(|r| r.id == reference.id) {
+            references.push(reference.clone());
+        }
+    }
+    references
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct Reference {
+    /// As written, like `#123`
+    pub id: String,
+    /// Rendered from `issue-url`
+    pub url: Option<String>,
+}
+
+static REFERENCE: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| regex::Regex::new(r"(^|[\s(\[,])([#!](\d+))\b").unwrap());
+
+fn parse_references(message: &str, url_template: Option<&str>) -> Vec<Reference> {
+    let mut references: Vec<Reference> = Vec::new();
+    for captures in REFERENCE.captures_iter(message) {
+        let id = &captures[2];
+        if references.iter().any(|r| r.id == id) {
+            continue;
+        }
+        let url = url_template.map(|t| t.replace("{{number}}", &captures[3]));
+        references.push(Reference {
+            id: id.to_owned(),
+            url,
+        });
+    }
+    references
+}
+
+/// Turn references in `text` into markdown links
+fn linkify(text: &str, references: &[Reference]) -> String {
+    REFERENCE
+        .replace_all(text, |captures: &regex::Captures<'_>| {
+            let prefix = &captures[1];
+            let id = &captures[2];
+            match references
+                .iter()
+                .find(|r| r.id == id)
+                .and_then(|r| r.url.as_deref())
+            {
+                Some(url) => format!("{prefix}[{id}]({url})"),
+                None => format!("{prefix}{id}"),
+            }
+        })
+        .into_owned()
+}
+
 fn write_status(status: Option<CommitStatus>) {
     if let Some(status) = status {
         let suffix;
@@ -298,60 +627,366 @@ fn write_status(status: Option<CommitStatus>) {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize)]
+struct PackageChanges {
+    name: String,
+    prior_tag: Option<String>,
+    version: String,
+    commits: Vec<PackageCommit>,
+    manifest_changes: Vec<String>,
+    contributors: Vec<String>,
+    suggested_bump: Option<BumpLevel>,
+}
+
 #[derive(Clone, Debug)]
 pub struct PackageCommit {
     pub id: git2::Oid,
     pub short_id: String,
     pub summary: String,
     pub message: String,
+    /// Release note from a `Changelog:` or `Release-Note:` trailer
+    pub note: Option<String>,
+    pub author: String,
+    /// Names from `Co-authored-by:` trailers
+    pub co_authors: Vec<String>,
+    /// Issues and pull requests mentioned in the message, like `#123` or `!45`
+    pub references: Vec<Reference>,
     pub paths: std::collections::BTreeSet<std::path::PathBuf>,
+    status: Option<CommitStatus>,
+}
+
+impl serde::Serialize for PackageCommit {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct as _;
+
+        let mut s = serializer.serialize_struct("PackageCommit", 10)?;
+        s.serialize_field("id", &self.id.to_string())?;
+        s.serialize_field("short_id", &self.short_id)?;
+        s.serialize_field("summary", &self.summary)?;
+        s.serialize_field("message", &self.message)?;
+        s.serialize_field("note", &self.note)?;
+        s.serialize_field("author", &self.author)?;
+        s.serialize_field("co_authors", &self.co_authors)?;
+        s.serialize_field("references", &self.references)?;
+        s.serialize_field("paths", &self.paths)?;
+        s.serialize_field("status", &self.status())?;
+        s.end()
+    }
 }
 
 impl PackageCommit {
-    pub fn status(&self) -> Option<CommitStatus> {
-        if let Some(status) = self.conventional_status() {
-            return status;
+    /// The release note, falling back to the summary
+    pub fn title(&self) -> &str {
+        self.note.as_deref().unwrap_or(&self.summary)
+    }
+
+    /// The release note, falling back to the change without the conventional-commit prefix
+    pub fn description(&self) -> &str {
+        if let Some(note) = self.note.as_deref() {
+            return note;
         }
+        git_conventional::Commit::parse(&self.message)
+            .map(|c| c.description())
+            .unwrap_or(&self.summary)
+    }
 
-        None
+    pub fn status(&self) -> Option<CommitStatus> {
+        self.status
     }
+}
 
-    fn conventional_status(&self) -> Option<Option<CommitStatus>> {
-        let parts = git_conventional::Commit::parse(&self.message).ok()?;
-        if parts.breaking() {
-            return Some(Some(CommitStatus::Breaking));
-        }
-
-        if [
-            git_conventional::Type::CHORE,
-            git_conventional::Type::TEST,
-            git_conventional::Type::STYLE,
-            git_conventional::Type::REFACTOR,
-            git_conventional::Type::REVERT,
-        ]
-        .contains(&parts.type_())
-        {
-            Some(Some(CommitStatus::Ignore))
-        } else if [
-            git_conventional::Type::DOCS,
-            git_conventional::Type::PERF,
-            git_conventional::Type::FIX,
-        ]
-        .contains(&parts.type_())
-        {
-            Some(Some(CommitStatus::Fix))
-        } else if [git_conventional::Type::FEAT].contains(&parts.type_()) {
-            Some(Some(CommitStatus::Feature))
-        } else {
-            Some(None)
+/// Release-note trailers, like `Changelog: skip`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Trailers {
+    skip: bool,
+    note: Option<String>,
+    status: Option<CommitStatus>,
+    co_authors: Vec<String>,
+}
+
+impl Trailers {
+    fn parse(message: &str) -> Self {
+        let mut trailers = Self::default();
+        let Ok(parsed) = git2::message_trailers_strs(message) else {
+            return trailers;
+        };
+        for (key, value) in parsed.iter() {
+            let value = value.trim();
+            if key.eq_ignore_ascii_case("Changelog") && value.eq_ignore_ascii_case("skip") {
+                trailers.skip = true;
+            } else if key.eq_ignore_ascii_case("Changelog")
+                || key.eq_ignore_ascii_case("Release-Note")
+            {
+                trailers.note = Some(value.to_owned());
+            } else if key.eq_ignore_ascii_case("Co-authored-by") {
+                let name = value
+                    .split_once('<')
+                    .map(|(n, _)| n.trim())
+                    .unwrap_or(value);
+                if !name.is_empty() && !trailers.co_authors.iter().any(|a| a == name) {
+                    trailers.co_authors.push(name.to_owned());
+                }
+            } else if key.eq_ignore_ascii_case("Breaking-Change") {
+                trailers.status = Some(CommitStatus::Breaking);
+            } else if key.eq_ignore_ascii_case("Release-Impact") {
+                match value.parse::<CommitStatus>() {
+                    Ok(status) => {
+                        if trailers.status != Some(CommitStatus::Breaking) {
+                            trailers.status = Some(status);
+                        }
+                    }
+                    Err(err) => log::debug!("ignoring `{key}: {value}`: {err}"),
+                }
+            }
         }
+        trailers
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+fn commit_status(message: &str, config: &crate::config::Config) -> Option<CommitStatus> {
+    if let Some(status) = conventional_status(message, config) {
+        return status;
+    }
+
+    None
+}
+
+fn conventional_status(
+    message: &str,
+    config: &crate::config::Config,
+) -> Option<Option<CommitStatus>> {
+    let parts = git_conventional::Commit::parse(message).ok()?;
+    if parts.breaking() {
+        return Some(Some(CommitStatus::Breaking));
+    }
+
+    Some(config.commit_type(
+        parts.type_().as_str(),
+        parts.scope().as_ref().map(|s| s.as_str()),
+    ))
+}
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
 pub enum CommitStatus {
     Ignore,
     Fix,
     Feature,
     Breaking,
 }
+
+impl std::str::FromStr for CommitStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ignore" => Ok(Self::Ignore),
+            "fix" => Ok(Self::Fix),
+            "feature" => Ok(Self::Feature),
+            "breaking" => Ok(Self::Breaking),
+            _ => Err(format!(
+                "invalid release impact `{s}`, expected one of `ignore`, `fix`, `feature`, `breaking`"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod trailers {
+        use super::*;
+
+        #[test]
+        fn none() {
+            let actual =
+                Trailers::parse("fix: Don't panic\n\nSigned-off-by: Ed <ed@example.com>\n");
+            assert_eq!(actual, Trailers::default());
+        }
+
+        #[test]
+        fn skip() {
+            let actual = Trailers::parse("chore: Bump deps\n\nChangelog: skip\n");
+            assert!(actual.skip);
+        }
+
+        #[test]
+        fn note() {
+            let actual =
+                Trailers::parse("fix: Off-by-one\n\nRelease-Note: Fixed truncated output\n");
+            assert_eq!(actual.note.as_deref(), Some("Fixed truncated output"));
+
+            let actual = Trailers::parse("fix: Off-by-one\n\nchangelog: Fixed truncated output\n");
+            assert_eq!(actual.note.as_deref(), Some("Fixed truncated output"));
+        }
+
+        #[test]
+        fn co_authors() {
+            let actual = Trailers::parse(
+                "feat: Pair on it\n\nCo-authored-by: Ann <ann@example.com>\nCo-Authored-By: Bo <bo@example.com>\nCo-authored-by: Ann <ann@example.com>\n",
+            );
+            assert_eq!(actual.co_authors, ["Ann", "Bo"]);
+        }
+
+        #[test]
+        fn status() {
+            let actual = Trailers::parse("feat: New API\n\nBreaking-Change: `old` was removed\n");
+            assert_eq!(actual.status, Some(CommitStatus::Breaking));
+
+            let actual = Trailers::parse("deps: Update\n\nRelease-Impact: fix\n");
+            assert_eq!(actual.status, Some(CommitStatus::Fix));
+
+            let actual = Trailers::parse("deps: Update\n\nRelease-Impact: major\n");
+            assert_eq!(actual.status, None);
+        }
+    }
+
+    mod references {
+        use super::*;
+
+        #[test]
+        fn parse() {
+            let actual = parse_references(
+                "fix: Don't panic (#123)\n\nSee !45, #123 and issue#9\n",
+                Some("https://example.com/issues/{{number}}"),
+            );
+            assert_eq!(
+                actual,
+                [
+                    Reference {
+                        id: "#123".to_owned(),
+                        url: Some("https://example.com/issues/123".to_owned()),
+                    },
+                    Reference {
+                        id: "!45".to_owned(),
+                        url: Some("https://example.com/issues/45".to_owned()),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn linkify_known() {
+            let references = parse_references("(#12)", Some("https://example.com/{{number}}"));
+            let actual = linkify("Don't panic (#12) (#123)", &references);
+            assert_eq!(actual, "Don't panic ([#12](https://example.com/12)) (#123)");
+        }
+    }
+
+    mod commit_filter {
+        use super::*;
+
+        fn commit(author_email: &str, message: &str) -> git::WalkedCommit {
+            git::WalkedCommit {
+                id: git2::Oid::zero(),
+                short_id: "0000000".to_owned(),
+                summary: message.lines().next().unwrap_or_default().to_owned(),
+                message: message.to_owned(),
+                author_name: "Someone".to_owned(),
+                author_email: author_email.to_owned(),
+                paths: Vec::new(),
+            }
+        }
+
+        fn filter(authors: &[&str], messages: &[&str]) -> CommitFilter {
+            let config = crate::config::Config {
+                changes_ignore_authors: Some(authors.iter().map(|a| (*a).to_owned()).collect()),
+                changes_ignore_messages: Some(messages.iter().map(|m| (*m).to_owned()).collect()),
+                ..Default::default()
+            };
+            CommitFilter::new(&config).unwrap()
+        }
+
+        #[test]
+        fn empty() {
+            let filter = filter(&[], &[]);
+            assert!(filter.is_empty());
+            assert!(!filter.is_ignored(&commit("bot@example.com", "chore(deps): Bump")));
+        }
+
+        #[test]
+        fn authors() {
+            let filter = filter(&[r"*\[bot\]@users.noreply.github.com"], &[]);
+            assert!(!filter.is_empty());
+            assert!(filter.is_ignored(&commit(
+                "renovate[bot]@users.noreply.github.com",
+                "fix: Bump"
+            )));
+            // Case insensitive
+            assert!(filter.is_ignored(&commit(
+                "Renovate[BOT]@Users.NoReply.GitHub.com",
+                "fix: Bump"
+            )));
+            assert!(!filter.is_ignored(&commit("dev@example.com", "fix: Bump")));
+            assert!(!filter.is_ignored(&commit("renovateb@users.noreply.github.com", "fix: Bump")));
+        }
+
+        #[test]
+        fn messages() {
+            let filter = filter(&[], &[r"^chore\(deps\):", "(?m)^Release-Bot: yes$"]);
+            assert!(filter.is_ignored(&commit("dev@example.com", "chore(deps): Bump")));
+            assert!(filter.is_ignored(&commit("dev@example.com", "fix: Bump\n\nRelease-Bot: yes")));
+            assert!(!filter.is_ignored(&commit("dev@example.com", "fix: chore(deps): Bump")));
+        }
+
+        #[test]
+        fn invalid() {
+            let config = crate::config::Config {
+                changes_ignore_messages: Some(vec!["(".to_owned()]),
+                ..Default::default()
+            };
+            assert!(CommitFilter::new(&config).is_err());
+            let config = crate::config::Config {
+                changes_ignore_authors: Some(vec!["[".to_owned()]),
+                ..Default::default()
+            };
+            assert!(CommitFilter::new(&config).is_err());
+        }
+    }
+
+    mod suggest_bump {
+        use super::*;
+
+        fn v(version: &str) -> semver::Version {
+            semver::Version::parse(version).unwrap()
+        }
+
+        #[test]
+        fn breaking() {
+            let breaking = CommitStatus::Breaking;
+            assert_eq!(
+                suggest_bump(&v("1.2.3"), breaking, false),
+                Some(BumpLevel::Major)
+            );
+            assert_eq!(
+                suggest_bump(&v("0.2.3"), breaking, false),
+                Some(BumpLevel::Minor)
+            );
+            assert_eq!(
+                suggest_bump(&v("0.0.3"), breaking, false),
+                Some(BumpLevel::Patch)
+            );
+            assert!(suggest_bump(&v("0.0.3"), breaking, true).is_none());
+        }
+
+        #[test]
+        fn feature() {
+            let feature = CommitStatus::Feature;
+            assert_eq!(
+                suggest_bump(&v("1.2.3"), feature, false),
+                Some(BumpLevel::Minor)
+            );
+            assert_eq!(
+                suggest_bump(&v("0.2.3"), feature, false),
+                Some(BumpLevel::Patch)
+            );
+            assert_eq!(
+                suggest_bump(&v("0.0.3"), feature, false),
+                Some(BumpLevel::Patch)
+            );
+        }
+    }
+}
@@ -0,0 +1,452 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::error::CargoResult;
+use crate::ops::git;
+use crate::steps::plan;
+
+/// Progress of an executed release, for `cargo release --resume`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Journal {
+    /// Release commit, once versions are written and committed
+    #[serde(default)]
+    pub commit: Option<String>,
+    /// Packages committed, when each package gets its own release commit
+    #[serde(default)]
+    pub committed: Vec<String>,
+    /// Packages published
+    #[serde(default)]
+    pub published: Vec<String>,
+    /// Packages with owners ensured
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Packages tagged
+    #[serde(default)]
+    pub tagged: Vec<String>,
+    /// Whether the push was done
+    #[serde(default)]
+    pub pushed: bool,
+    /// What is being released, recorded before anything is changed
+    pub plan: plan::ReleasePlan,
+}
+
+impl Journal {
+    pub fn new(plan: plan::ReleasePlan) -> Self {
+        Self {
+            commit: None,
+            committed: Vec::new(),
+            published: Vec::new(),
+            owners: Vec::new(),
+            tagged: Vec::new(),
+            pushed: false,
+            plan,
+        }
+    }
+
+    pub fn path(ws_meta: &cargo_metadata::Metadata) -> PathBuf {
+        ws_meta
+            .target_directory
+            .as_std_path()
+            .join("release-journal.toml")
+    }
+
+    pub fn load(path: &Path) -> CargoResult<Self> {
+        let journal = std::fs::read_to_string(path).map_err(|err| {
+            anyhow::format_err!("no release to resume, {}: {err}", path.display())
+        })?;
+        let journal = toml::from_str(&journal)
+            .map_err(|err| anyhow::format_err!("failed to parse {}: {err}", path.display()))?;
+        Ok(journal)
+    }
+
+    pub fn save(&self, path: &Path) -> CargoResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Error if `HEAD` is not based on the recorded progress
+    pub fn verify_head(&self, dir: &Path) -> CargoResult<()> {
+        match self.commit.as_deref() {
+            Some(commit) => {
+                if !git::head_descends_from(dir, commit)? {
+                    anyhow::bail!("HEAD is not based on {commit}, the release commit");
+                }
+                Ok(())
+            }
+            None => self.plan.verify_head(dir),
+        }
+    }
+
+    /// Restore the planned release of `pkg`
+    ///
+    /// Once committed, the manifest already has the planned version.
+    pub fn resume(&self, pkg: &mut plan::PackageRelease) -> CargoResult<()> {
+        let crate_name = pkg.meta.name.as_str();
+        if self.commit.is_none() && !self.committed.iter().any(|c| c == crate_name) {
+            return self.plan.apply(pkg);
+        }
+
+        let Some(planned) = self.plan.package.iter().find(|p| p.name == crate_name) else {
+            log::debug!("not in release, skipping {crate_name}");
+            pkg.config.release = Some(false);
+            return Ok(());
+        };
+        let version = planned
+            .planned_version
+            .as_deref()
+            .unwrap_or(&planned.initial_version);
+        if pkg.initial_version.full_version_string != version {
+            anyhow::bail!(
+                "{crate_name} is at {} rather than the released {version}",
+                pkg.initial_version.full_version_string
+            );
+        }
+        pkg.planned_version = None;
+        pkg.prior_tag = planned.prior_tag.clone();
+        planned.apply_config(pkg);
+        Ok(())
+    }
+
+    /// Forget recorded tags that are missing from git and record tags made before a failure
+    ///
+    /// Tags are only made after the release commit, so unrecorded tags are only looked up
+    /// once it exists.
+    pub fn verify_tags(&mut self, pkgs: &[plan::PackageRelease]) -> CargoResult<()> {
+        for pkg in pkgs {
+            let Some(tag) = pkg.planned_tag.as_deref() else {
+                continue;
+            };
+            let name = &pkg.meta.name;
+            let recorded = self.tagged.contains(name);
+            if !recorded && self.commit.is_none() {
+                continue;
+            }
+            let exists = git::tag_exists(&pkg.package_root, tag)?;
+            if recorded && !exists {
+                let _ = crate::ops::shell::warn(format!(
+                    "{name} was tagged {tag} but the tag is missing; tagging it again"
+                ));
+                self.tagged.retain(|t| t != name);
+            } else if !recorded && exists {
+                log::debug!("{name} is already tagged {tag}, skipping it");
+                self.tagged.push(name.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget recorded publishes that are missing from the index and record publishes made
+    /// before a failure
+    ///
+    /// Publishing only happens after the release commit, so unrecorded publishes are only
+    /// looked up once it exists.
+    pub fn verify_published(
+        &mut self,
+        pkgs: &[plan::PackageRelease],
+        is_published: &mut dyn FnMut(&plan::PackageRelease) -> bool,
+    ) {
+        for pkg in pkgs.iter().filter(|p| p.config.publish()) {
+            let name = &pkg.meta.name;
+            let recorded = self.published.contains(name);
+            if !recorded && self.commit.is_none() {
+                continue;
+            }
+            let published = is_published(pkg);
+            let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+            if recorded && !published {
+                let _ = crate::ops::shell::warn(format!(
+                    "{name} was published but {} is missing from the index; publishing it again",
+                    version.full_version_string
+                ));
+                self.published.retain(|p| p != name);
+            } else if !recorded && published {
+                log::debug!(
+                    "{name} {} is already published, skipping it",
+                    version.full_version_string
+                );
+                self.published.push(name.clone());
+            }
+        }
+    }
+
+    /// `pkgs` not recorded in `done`
+    pub fn pending(pkgs: &[plan::PackageRelease], done: &[String]) -> Vec<plan::PackageRelease> {
+        pkgs.iter()
+            .filter(|p| !done.contains(&p.meta.name))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[allow(unused_imports)] // Not being detected
+    use assert_fs::prelude::*;
+
+    /// A committed copy of `tests/fixtures/pure_ws`, with its packages
+    fn workspace() -> (assert_fs::TempDir, Vec<plan::PackageRelease>) {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.copy_from("tests/fixtures/pure_ws", &["**"]).unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+        let pkgs = packages(&temp);
+        (temp, pkgs)
+    }
+
+    fn packages(temp: &assert_fs::TempDir) -> Vec<plan::PackageRelease> {
+        let meta = cargo_metadata::MetadataCommand::new()
+            .manifest_path(temp.child("Cargo.toml").path())
+            .exec()
+            .unwrap();
+        let args = crate::config::ConfigArgs {
+            isolated: true,
+            ..Default::default()
+        };
+        plan::load(&args, &meta).unwrap().into_values().collect()
+    }
+
+    /// A journal releasing `pkgs` at 0.2.0
+    fn journal(temp: &assert_fs::TempDir, pkgs: &[plan::PackageRelease]) -> Journal {
+        let meta = cargo_metadata::MetadataCommand::new()
+            .manifest_path(temp.child("Cargo.toml").path())
+            .exec()
+            .unwrap();
+        let pkgs = pkgs
+            .iter()
+            .cloned()
+            .map(|mut pkg| {
+                pkg.planned_version = Some(semver::Version::new(0, 2, 0).into());
+                pkg
+            })
+            .collect::<Vec<_>>();
+        Journal::new(plan::ReleasePlan::new(&meta, &pkgs).unwrap())
+    }
+
+    fn find<'p>(pkgs: &'p mut [plan::PackageRelease], name: &str) -> &'p mut plan::PackageRelease {
+        pkgs.iter_mut().find(|p| p.meta.name == name).unwrap()
+    }
+
+    mod resume {
+        use super::*;
+
+        #[test]
+        fn uncommitted() {
+            let (temp, mut pkgs) = workspace();
+            let journal = journal(&temp, &pkgs[..1]);
+
+            let pkg = &mut pkgs[0];
+            journal.resume(pkg).unwrap();
+            assert!(pkg.config.release());
+            assert_eq!(
+                pkg.planned_version.as_ref().unwrap().full_version_string,
+                "0.2.0"
+            );
+
+            let pkg = &mut pkgs[1];
+            journal.resume(pkg).unwrap();
+            assert!(!pkg.config.release());
+
+            temp.close().unwrap();
+        }
+
+        #[test]
+        fn committed() {
+            let (temp, pkgs) = workspace();
+            let mut journal = journal(&temp, &pkgs);
+            journal.committed = vec!["a".to_owned()];
+
+            let mut pkgs = packages(&temp);
+            let err = journal.resume(find(&mut pkgs, "a")).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "a is at 0.1.0 rather than the released 0.2.0"
+            );
+
+            crate::ops::cargo::set_package_version(
+                temp.child("a/Cargo.toml").path(),
+                "0.2.0",
+                false,
+            )
+            .unwrap();
+            let mut pkgs = packages(&temp);
+            let pkg = find(&mut pkgs, "a");
+            journal.resume(pkg).unwrap();
+            assert!(pkg.planned_version.is_none());
+            assert_eq!(pkg.initial_version.full_version_string, "0.2.0");
+
+            // Not yet committed, so it is still planned
+            let pkg = find(&mut pkgs, "b");
+            journal.resume(pkg).unwrap();
+            assert_eq!(
+                pkg.planned_version.as_ref().unwrap().full_version_string,
+                "0.2.0"
+            );
+
+            temp.close().unwrap();
+        }
+
+        #[test]
+        fn manifest_changed() {
+            let (temp, pkgs) = workspace();
+            let journal = journal(&temp, &pkgs);
+
+            temp.child("a/Cargo.toml")
+                .write_str("[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n")
+                .unwrap();
+            let mut pkgs = packages(&temp);
+            let err = journal.resume(find(&mut pkgs, "a")).unwrap_err();
+            assert_eq!(err.to_string(), "a's manifest changed since it was planned");
+
+            temp.close().unwrap();
+        }
+    }
+
+    mod verify_tags {
+        use super::*;
+
+        fn tag(temp: &assert_fs::TempDir, name: &str) {
+            let repo = git2::Repository::open(temp.path()).unwrap();
+            let head = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.tag_lightweight(name, head.as_object(), false).unwrap();
+        }
+
+        fn planned_tags(mut pkgs: Vec<plan::PackageRelease>) -> Vec<plan::PackageRelease> {
+            for pkg in &mut pkgs {
+                pkg.planned_tag = Some(format!("{}-v0.2.0", pkg.meta.name));
+            }
+            pkgs
+        }
+
+        #[test]
+        fn forgets_missing() {
+            let (temp, pkgs) = workspace();
+            let pkgs = planned_tags(pkgs);
+            let mut journal = journal(&temp, &pkgs);
+            journal.commit = Some("c0ffee".to_owned());
+            journal.tagged = vec!["a".to_owned(), "b".to_owned()];
+            tag(&temp, "a-v0.2.0");
+
+            journal.verify_tags(&pkgs).unwrap();
+            assert_eq!(journal.tagged, ["a"]);
+
+            temp.close().unwrap();
+        }
+
+        #[test]
+        fn records_unrecorded() {
+            let (temp, pkgs) = workspace();
+            let pkgs = planned_tags(pkgs);
+            let mut journal = journal(&temp, &pkgs);
+            journal.commit = Some("c0ffee".to_owned());
+            tag(&temp, "b-v0.2.0");
+
+            journal.verify_tags(&pkgs).unwrap();
+            assert_eq!(journal.tagged, ["b"]);
+
+            temp.close().unwrap();
+        }
+
+        #[test]
+        fn uncommitted() {
+            let (temp, pkgs) = workspace();
+            let pkgs = planned_tags(pkgs);
+            let mut journal = journal(&temp, &pkgs);
+            tag(&temp, "b-v0.2.0");
+
+            journal.verify_tags(&pkgs).unwrap();
+            assert!(journal.tagged.is_empty());
+
+            temp.close().unwrap();
+        }
+    }
+
+    mod verify_published {
+        use super::*;
+
+        #[test]
+        fn forgets_missing() {
+            let (temp, pkgs) = workspace();
+            let mut journal = journal(&temp, &pkgs);
+            journal.commit = Some("c0ffee".to_owned());
+            journal.published = vec!["a".to_owned(), "b".to_owned()];
+
+            journal.verify_published(&pkgs, &mut |pkg| pkg.meta.name == "a");
+            assert_eq!(journal.published, ["a"]);
+
+            temp.close().unwrap();
+        }
+
+        #[test]
+        fn records_unrecorded() {
+            let (temp, pkgs) = workspace();
+            let mut journal = journal(&temp, &pkgs);
+            journal.commit = Some("c0ffee".to_owned());
+
+            journal.verify_published(&pkgs, &mut |pkg| pkg.meta.name != "c");
+            assert_eq!(journal.published, ["a", "b"]);
+
+            temp.close().unwrap();
+        }
+
+        #[test]
+        fn uncommitted() {
+            let (temp, pkgs) = workspace();
+            let mut journal = journal(&temp, &pkgs);
+
+            journal.verify_published(&pkgs, &mut |_| true);
+            assert!(journal.published.is_empty());
+
+            temp.close().unwrap();
+        }
+    }
+
+    mod pending {
+        use super::*;
+
+        #[test]
+        fn skips_done() {
+            let (temp, pkgs) = workspace();
+
+            let pending = Journal::pending(&pkgs, &["b".to_owned()]);
+            let names = pending
+                .iter()
+                .map(|p| p.meta.name.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["a", "c"]);
+
+            temp.close().unwrap();
+        }
+    }
+
+    mod save {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let (temp, pkgs) = workspace();
+            let mut journal = journal(&temp, &pkgs);
+            journal.commit = Some("c0ffee".to_owned());
+            journal.pushed = true;
+            let path = temp.child("target/release-journal.toml");
+
+            journal.save(path.path()).unwrap();
+            let loaded = Journal::load(path.path()).unwrap();
+            assert_eq!(loaded.commit.as_deref(), Some("c0ffee"));
+            assert!(loaded.pushed);
+
+            temp.close().unwrap();
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::str::FromStr;
 
+pub mod apply;
 pub mod changes;
 pub mod commit;
 pub mod config;
 pub mod hook;
+pub mod journal;
 pub mod owner;
 pub mod plan;
 pub mod publish;
@@ -40,6 +42,36 @@ pub fn verify_git_is_clean(
     Ok(success)
 }
 
+/// Check that tag-versioned packages have a tag to take their version from, unless `absolute`
+pub fn verify_tag_versions(
+    pkgs: &[plan::PackageRelease],
+    absolute: bool,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut missing = false;
+    for pkg in pkgs.iter().filter(|p| !absolute && p.missing_tag_version()) {
+        let _ = crate::ops::shell::log(
+            level,
+            format!(
+                "no tag found for the version of `{}`, only the placeholder `{}` in its manifest; pass an absolute version for its first release",
+                pkg.meta.name, pkg.initial_version.full_version_string
+            ),
+        );
+        missing = true;
+    }
+    if missing && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
 pub fn verify_tags_missing(
     pkgs: &[plan::PackageRelease],
     dry_run: bool,
@@ -210,6 +242,140 @@ pub fn verify_monotonically_increasing(
     Ok(success)
 }
 
+/// Report breaking manifest changes released with a compatible version, per `manifest-breaking`
+pub fn verify_manifest_changes(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut unmarked_breaking = false;
+    for pkg in pkgs {
+        let level = match pkg.config.manifest_breaking() {
+            crate::config::ManifestBreaking::Error => log::Level::Error,
+            crate::config::ManifestBreaking::Warn => log::Level::Warn,
+            crate::config::ManifestBreaking::Ignore => continue,
+        };
+        let Some(prior_tag_name) = pkg.prior_tag.as_deref() else {
+            continue;
+        };
+        let changes = changes::manifest_changes(ws_meta, pkg, prior_tag_name)?;
+        let Some(prior_version) = changes.prior_version.as_ref() else {
+            continue;
+        };
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        if !changes.breaking.is_empty()
+            && crate::ops::manifest::is_compatible(prior_version, &version.full_version)
+        {
+            let crate_name = pkg.meta.name.as_str();
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "{} {} is semver-compatible with {} but its manifest has breaking changes:\n  {}",
+                    crate_name,
+                    version.full_version,
+                    prior_version,
+                    changes.breaking.join("\n  ")
+                ),
+            );
+            unmarked_breaking |= level == log::Level::Error;
+        }
+    }
+    if unmarked_breaking {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// Check dependents under `dependent-version = "error"`, and that `fix`/`upgrade` can rewrite
+/// them, before any manifest is written
+pub fn verify_dependent_versions(
+    ws_meta: &cargo_metadata::Metadata,
+    selected_pkgs: &[plan::PackageRelease],
+    excluded_pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let workspace_version = selected_pkgs
+        .iter()
+        .filter(|p| p.config.shared_version() == Some(crate::config::SharedVersion::WORKSPACE))
+        .find_map(|p| p.planned_version.as_ref());
+    let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
+
+    let mut mismatched = false;
+    let mut unrewritable = false;
+    for pkg in selected_pkgs.iter().chain(excluded_pkgs) {
+        let dependent_version = pkg.config.dependent_version();
+        if !matches!(
+            dependent_version,
+            crate::config::DependentVersion::Error
+                | crate::config::DependentVersion::Fix
+                | crate::config::DependentVersion::Upgrade
+        ) || pkg.versioned_by_tag()
+        {
+            continue;
+        }
+        let version =
+            if pkg.config.shared_version() == Some(crate::config::SharedVersion::WORKSPACE) {
+                workspace_version
+            } else {
+                pkg.planned_version.as_ref()
+            };
+        let Some(version) = version else {
+            continue;
+        };
+        let manifests = std::iter::once(("workspace", workspace_path.clone())).chain(
+            version::find_ws_members(ws_meta)
+                .map(|d| (d.name.as_str(), d.manifest_path.as_std_path().to_owned())),
+        );
+        for (manifest_name, manifest_path) in manifests {
+            if dependent_version == crate::config::DependentVersion::Error {
+                let problems = crate::ops::cargo::check_dependency_req(
+                    manifest_name,
+                    &manifest_path,
+                    &pkg.package_root,
+                    &pkg.meta.name,
+                    &version.full_version,
+                    crate::config::DependentVersion::Error,
+                )?;
+                for problem in problems {
+                    let _ = crate::ops::shell::log(level, problem);
+                    mismatched = true;
+                }
+            } else {
+                // Rewrites happen while manifests are being written, so catch failures first
+                let problems = crate::ops::cargo::check_dependency_rewrite(
+                    manifest_name,
+                    &manifest_path,
+                    &pkg.package_root,
+                    &pkg.meta.name,
+                    &version.full_version,
+                    dependent_version,
+                )?;
+                for problem in problems {
+                    let _ = crate::ops::shell::error(problem);
+                    unrewritable = true;
+                }
+            }
+        }
+    }
+    if unrewritable || (mismatched && level == log::Level::Error) {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
 pub fn verify_rate_limit(
     pkgs: &[plan::PackageRelease],
     index: &mut crate::ops::index::CratesIoIndex,
@@ -389,6 +555,94 @@ pub fn warn_changed(
     Ok(())
 }
 
+/// Select workspace members by their changes, rather than by name
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct ChangedArgs {
+    /// Process the workspace members with files changed since their last tag
+    #[arg(long, group = "changed_selection", conflicts_with = "package")]
+    changed: bool,
+
+    /// Process the workspace members with files changed since REF
+    #[arg(
+        long,
+        value_name = "REF",
+        group = "changed_selection",
+        conflicts_with = "package"
+    )]
+    changed_since: Option<String>,
+
+    /// Also process the dependents of changed packages
+    #[arg(long, requires = "changed_selection")]
+    with_dependents: bool,
+}
+
+impl ChangedArgs {
+    /// Like [`clap_cargo::Workspace::partition_packages`], selecting only changed packages with
+    /// `--changed` or `--changed-since`
+    ///
+    /// `pkgs` must be in dependency order so `--with-dependents` is transitive.
+    pub fn partition_packages<'m>(
+        &self,
+        workspace: &clap_cargo::Workspace,
+        ws_meta: &'m cargo_metadata::Metadata,
+        pkgs: &indexmap::IndexMap<cargo_metadata::PackageId, plan::PackageRelease>,
+    ) -> CargoResult<(
+        Vec<&'m cargo_metadata::Package>,
+        Vec<&'m cargo_metadata::Package>,
+    )> {
+        if !self.changed && self.changed_since.is_none() {
+            return Ok(workspace.partition_packages(ws_meta));
+        }
+
+        let mut changed_pkgs = std::collections::HashSet::new();
+        for pkg in pkgs.values() {
+            if !pkg.config.release() {
+                continue;
+            }
+            let crate_name = pkg.meta.name.as_str();
+            if !changed_pkgs.contains(&pkg.meta.id) {
+                let since_ref = self.changed_since.as_deref().or(pkg.prior_tag.as_deref());
+                if let Some(since_ref) = since_ref {
+                    let changed =
+                        version::changed_since(ws_meta, pkg, since_ref).ok_or_else(|| {
+                            anyhow::format_err!(
+                                "cannot detect changes for {crate_name} since {since_ref}"
+                            )
+                        })?;
+                    if changed.is_empty() {
+                        log::trace!("not selecting {crate_name}, no changes since {since_ref}");
+                        continue;
+                    }
+                    log::debug!("selecting {crate_name}, files changed since {since_ref}");
+                } else {
+                    log::debug!("selecting {crate_name}, no tag found");
+                }
+                changed_pkgs.insert(pkg.meta.id.clone());
+            }
+            if self.with_dependents {
+                for dependent in &pkg.dependents {
+                    let is_dev_only = dependent.pkg.dependencies.iter().all(|d| {
+                        d.name != pkg.meta.name
+                            || d.kind == cargo_metadata::DependencyKind::Development
+                    });
+                    if !is_dev_only && changed_pkgs.insert(dependent.pkg.id.clone()) {
+                        log::debug!(
+                            "selecting {}, it depends on {crate_name}",
+                            dependent.pkg.name
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(ws_meta.packages.iter().partition(|p| {
+            ws_meta.workspace_members.contains(&p.id)
+                && changed_pkgs.contains(&p.id)
+                && !workspace.exclude.contains(&p.name)
+        }))
+    }
+}
+
 pub fn find_shared_versions(
     pkgs: &[plan::PackageRelease],
 ) -> Result<Option<plan::Version>, crate::error::CliError> {
@@ -507,6 +761,12 @@ pub fn finish(failed: bool, dry_run: bool) -> Result<(), crate::error::CliError>
 pub enum TargetVersion {
     Relative(BumpLevel),
     Absolute(semver::Version),
+    /// Next `YEAR.MONTH.PATCH` version
+    Calendar,
+    /// Increase an arbitrary pre-version (x.y.z-<ident>.M)
+    ///
+    /// With a `pre*` level, start the pre-version on a new release instead.
+    Prerelease(Option<BumpLevel>, String),
 }
 
 impl TargetVersion {
@@ -514,11 +774,12 @@ impl TargetVersion {
         &self,
         current: &semver::Version,
         metadata: Option<&str>,
+        prerelease_channels: &[&str],
     ) -> CargoResult<Option<plan::Version>> {
         match self {
             TargetVersion::Relative(bump_level) => {
                 let mut potential_version = current.to_owned();
-                bump_level.bump_version(&mut potential_version, metadata)?;
+                bump_level.bump_version(&mut potential_version, metadata, prerelease_channels)?;
                 if potential_version != *current {
                     let full_version = potential_version;
                     let version = plan::Version::from(full_version);
@@ -527,6 +788,28 @@ impl TargetVersion {
                     Ok(None)
                 }
             }
+            TargetVersion::Calendar => {
+                let mut full_version = crate::ops::version::calendar_version(
+                    current,
+                    crate::ops::replace::NOW.as_str(),
+                )?;
+                if let Some(metadata) = metadata {
+                    full_version.metadata(metadata)?;
+                }
+                Ok(Some(plan::Version::from(full_version)))
+            }
+            TargetVersion::Prerelease(level, id) => {
+                let mut full_version = current.to_owned();
+                if let Some(level) = level {
+                    level.start_prerelease(&mut full_version, id)?;
+                } else {
+                    full_version.increment_prerelease(id, prerelease_channels)?;
+                }
+                if let Some(metadata) = metadata {
+                    full_version.metadata(metadata)?;
+                }
+                Ok(Some(plan::Version::from(full_version)))
+            }
             TargetVersion::Absolute(version) => {
                 let mut full_version = version.to_owned();
                 if full_version.build.is_empty() {
@@ -562,6 +845,15 @@ impl std::fmt::Display for TargetVersion {
             TargetVersion::Absolute(version) => {
                 write!(f, "{version}")
             }
+            TargetVersion::Calendar => {
+                write!(f, "calver")
+            }
+            TargetVersion::Prerelease(Some(level), id) => {
+                write!(f, "{level}:{id}")
+            }
+            TargetVersion::Prerelease(None, id) => {
+                write!(f, "pre:{id}")
+            }
         }
     }
 }
@@ -572,6 +864,30 @@ impl FromStr for TargetVersion {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(bump_level) = BumpLevel::from_str(s) {
             Ok(TargetVersion::Relative(bump_level))
+        } else if s == "calver" {
+            Ok(TargetVersion::Calendar)
+        } else if let Some((level, id)) = s.split_once(':') {
+            let level = match level {
+                "pre" => None,
+                _ => match BumpLevel::from_str(level) {
+                    Ok(
+                        level @ (BumpLevel::Premajor | BumpLevel::Preminor | BumpLevel::Prepatch),
+                    ) => Some(level),
+                    _ => {
+                        return Err(format!(
+                            "invalid level `{level}`, expected `pre`, `premajor`, `preminor`, or `prepatch`"
+                        ));
+                    }
+                },
+            };
+            let valid = !id.is_empty()
+                && id.split('.').all(|p| {
+                    !p.is_empty() && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                });
+            if !valid {
+                return Err(format!("invalid pre-release identifier `{id}`"));
+            }
+            Ok(TargetVersion::Prerelease(level, id.to_owned()))
         } else {
             Ok(TargetVersion::Absolute(
                 semver::Version::parse(s).map_err(|e| e.to_string())?,
@@ -610,7 +926,12 @@ impl clap::builder::TypedValueParser for TargetVersionParser {
         let inner_parser = clap::builder::EnumValueParser::<BumpLevel>::new();
         #[allow(clippy::needless_collect)] // Erasing a lifetime
         inner_parser.possible_values().map(|ps| {
-            let ps = ps.collect::<Vec<_>>();
+            let ps = ps
+                .chain([clap::builder::PossibleValue::new("calver")
+                    .help("Set a YEAR.MONTH.PATCH version from today's date")])
+                .chain([clap::builder::PossibleValue::new("pre:<ident>")
+                    .help("Increase the <ident> pre-version (x.y.z-<ident>.M)")])
+                .collect::<Vec<_>>();
             let ps: Box<dyn Iterator<Item = clap::builder::PossibleValue> + '_> =
                 Box::new(ps.into_iter());
             ps
@@ -618,7 +939,8 @@ impl clap::builder::TypedValueParser for TargetVersionParser {
     }
 }
 
-#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
 #[value(rename_all = "kebab-case")]
 pub enum BumpLevel {
     /// Increase the major version (x.0.0)
@@ -635,6 +957,14 @@ pub enum BumpLevel {
     Beta,
     /// Increase the alpha pre-version (x.y.z-alpha.M)
     Alpha,
+    /// Increase the major version and start a pre-version (x.0.0-alpha.1)
+    Premajor,
+    /// Increase the minor version and start a pre-version (x.y.0-alpha.1)
+    Preminor,
+    /// Increase the patch version and start a pre-version (x.y.z-alpha.1)
+    Prepatch,
+    /// Pick major, minor, or patch from the conventional commits since the last tag
+    Auto,
 }
 
 impl std::fmt::Display for BumpLevel {
@@ -668,6 +998,7 @@ impl BumpLevel {
         self,
         version: &mut semver::Version,
         metadata: Option<&str>,
+        prerelease_channels: &[&str],
     ) -> CargoResult<()> {
         match self {
             BumpLevel::Major => {
@@ -689,13 +1020,22 @@ impl BumpLevel {
                 }
             }
             BumpLevel::Rc => {
-                version.increment_rc()?;
+                version.increment_prerelease("rc", prerelease_channels)?;
             }
             BumpLevel::Beta => {
-                version.increment_beta()?;
+                version.increment_prerelease("beta", prerelease_channels)?;
             }
             BumpLevel::Alpha => {
-                version.increment_alpha()?;
+                version.increment_prerelease("alpha", prerelease_channels)?;
+            }
+            BumpLevel::Premajor | BumpLevel::Preminor | BumpLevel::Prepatch => {
+                let channel = prerelease_channels
+                    .first()
+                    .ok_or_else(|| anyhow::format_err!("no `prerelease-channels` to start"))?;
+                self.start_prerelease(version, channel)?;
+            }
+            BumpLevel::Auto => {
+                anyhow::bail!("`auto` must be resolved from the commit history");
             }
         };
 
@@ -705,4 +1045,62 @@ impl BumpLevel {
 
         Ok(())
     }
+
+    /// Bump a `pre*` level and start the `id` pre-version on it
+    pub fn start_prerelease(self, version: &mut semver::Version, id: &str) -> CargoResult<()> {
+        match self {
+            BumpLevel::Premajor => version.increment_major(),
+            BumpLevel::Preminor => version.increment_minor(),
+            BumpLevel::Prepatch => version.increment_patch(),
+            _ => anyhow::bail!("`{self}` cannot start a pre-release"),
+        }
+        version.pre = semver::Prerelease::new(&format!("{id}.1"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod target_version {
+        use super::*;
+
+        #[track_caller]
+        fn assert_bump(target: &str, current: &str, expected: &str) {
+            let target = TargetVersion::from_str(target).unwrap();
+            let current = semver::Version::parse(current).unwrap();
+            let actual = target
+                .bump(
+                    &current,
+                    None,
+                    crate::ops::version::DEFAULT_PRERELEASE_CHANNELS,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(actual.full_version_string, expected);
+        }
+
+        #[test]
+        fn start_prerelease() {
+            assert_bump("premajor", "1.4.2", "2.0.0-alpha.1");
+            assert_bump("preminor", "1.4.2", "1.5.0-alpha.1");
+            assert_bump("prepatch", "1.4.2", "1.4.3-alpha.1");
+            assert_bump("premajor:rc", "1.4.2", "2.0.0-rc.1");
+            assert_bump("premajor:nightly", "2.0.0-rc.1", "3.0.0-nightly.1");
+        }
+
+        #[test]
+        fn increment_prerelease() {
+            assert_bump("pre:nightly", "1.4.2", "1.4.3-nightly.1");
+            assert_bump("pre:nightly", "1.4.3-nightly.1", "1.4.3-nightly.2");
+        }
+
+        #[test]
+        fn invalid() {
+            assert!(TargetVersion::from_str("major:rc").is_err());
+            assert!(TargetVersion::from_str("pre:").is_err());
+            assert!(TargetVersion::from_str("pre:a_b").is_err());
+        }
+    }
 }
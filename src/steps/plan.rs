@@ -1,13 +1,19 @@
 use std::path::Path;
 use std::path::PathBuf;
 
+use itertools::Itertools as _;
+
 use crate::config;
 use crate::error::CargoResult;
 use crate::ops::cargo;
 use crate::ops::git;
+use crate::ops::replace::NOW;
 use crate::ops::replace::Template;
 use crate::ops::version::VersionExt as _;
 
+/// `--metadata` value for deriving the metadata from git
+const AUTO_METADATA: &str = "auto";
+
 pub fn load(
     args: &config::ConfigArgs,
     ws_meta: &cargo_metadata::Metadata,
@@ -22,6 +28,93 @@ pub fn load(
         .collect()
 }
 
+/// Bump dependents of packages with breaking bumps, per `cascade-bumps`
+///
+/// Public dependents get a breaking bump and private dependents a patch bump.  Public dependents
+/// left out of the package selection (`deselected`) are pulled back in, like `--with-dependents`.
+/// `pkgs` must be in dependency order so cascades are transitive.
+pub fn cascade_bumps(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &mut indexmap::IndexMap<cargo_metadata::PackageId, PackageRelease>,
+    deselected: &std::collections::HashSet<cargo_metadata::PackageId>,
+    metadata: Option<&str>,
+) -> CargoResult<()> {
+    let ids = pkgs.keys().cloned().collect::<Vec<_>>();
+    for id in ids {
+        let pkg = &pkgs[&id];
+        if !pkg.config.release() || !pkg.config.cascade_bumps() {
+            continue;
+        }
+        let Some(planned_version) = pkg.planned_version.as_ref() else {
+            continue;
+        };
+        if crate::ops::manifest::is_compatible(
+            &pkg.initial_version.bare_version,
+            &planned_version.bare_version,
+        ) {
+            continue;
+        }
+        let name = pkg.meta.name.clone();
+        let reason = format!(
+            "{} from {} to {}",
+            name, pkg.initial_version.full_version_string, planned_version.full_version_string
+        );
+        let dependents = pkg
+            .dependents
+            .iter()
+            .filter(|d| {
+                d.pkg.dependencies.iter().any(|dep| {
+                    dep.name == name && dep.kind != cargo_metadata::DependencyKind::Development
+                })
+            })
+            .map(|d| Ok((d.pkg.id.clone(), is_public_dependency(&d.pkg, &name)?)))
+            .collect::<CargoResult<Vec<_>>>()?;
+
+        for (dependent_id, public) in dependents {
+            let Some(dependent) = pkgs.get_mut(&dependent_id) else {
+                continue;
+            };
+            let dependent_name = dependent.meta.name.clone();
+            let public = public || dependent.config.public_dependencies().contains(&name);
+            if !dependent.config.release() {
+                if public && deselected.contains(&dependent_id) {
+                    log::debug!("selecting {dependent_name}, it publicly depends on {name}");
+                    dependent.config.release = Some(true);
+                } else {
+                    let _ = crate::ops::shell::warn(format!(
+                        "not cascading the {name} bump to {dependent_name}, it is not being released"
+                    ));
+                    continue;
+                }
+            }
+            let initial = &dependent.initial_version.bare_version;
+            let sufficient = dependent.planned_version.as_ref().is_some_and(|p| {
+                !public || !crate::ops::manifest::is_compatible(initial, &p.bare_version)
+            });
+            if sufficient {
+                continue;
+            }
+            let level = match (public, initial.major, initial.minor) {
+                (false, _, _) => super::BumpLevel::Patch,
+                (true, 0, 0) => super::BumpLevel::Patch,
+                (true, 0, _) => super::BumpLevel::Minor,
+                (true, _, _) => super::BumpLevel::Major,
+            };
+            let _ = crate::ops::shell::status(
+                "Cascading",
+                format!(
+                    "{} bump to {dependent_name} for its {} dependency on {reason}",
+                    level,
+                    if public { "public" } else { "private" }
+                ),
+            );
+            dependent.bump(ws_meta, &super::TargetVersion::Relative(level), metadata)?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn plan(
     mut pkgs: indexmap::IndexMap<cargo_metadata::PackageId, PackageRelease>,
 ) -> CargoResult<indexmap::IndexMap<cargo_metadata::PackageId, PackageRelease>> {
@@ -73,7 +166,7 @@ pub fn plan(
     Ok(pkgs)
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PackageRelease {
     pub meta: cargo_metadata::Package,
     pub manifest_path: PathBuf,
@@ -135,6 +228,7 @@ impl PackageRelease {
                 .map(|p| p.starts_with("tests"))
                 .unwrap_or(false)
         });
+        filter_changes(&mut package_content, &package_root, &config)?;
         let features = config.features();
         let dependents = find_dependents(ws_meta, pkg_meta)
             .map(|(pkg, dep)| Dependency {
@@ -144,29 +238,17 @@ impl PackageRelease {
             .collect();
 
         let is_root = git_root == package_root;
-        let initial_version = Version::from(pkg_meta.version.clone());
+        let mut initial_version = Version::from(pkg_meta.version.clone());
         let tag_name = config.tag_name();
         let tag_prefix = config.tag_prefix(is_root);
         let name = pkg_meta.name.as_str();
 
-        let initial_tag = render_tag(
-            tag_name,
-            tag_prefix,
-            name,
-            &initial_version,
-            &initial_version,
-        );
-        let prior_tag = if git::tag_exists(&package_root, &initial_tag)? {
-            Some(initial_tag)
-        } else {
-            let tag_name = config.tag_name();
-            let tag_prefix = config.tag_prefix(is_root);
-            let name = meta.name.as_str();
+        let find_last_tag = |lightweight| {
             let tag_glob = render_tag_glob(tag_name, tag_prefix, name);
             match globset::Glob::new(&tag_glob) {
                 Ok(tag_glob) => {
                     let tag_glob = tag_glob.compile_matcher();
-                    git::find_last_tag(&package_root, &tag_glob)
+                    git::find_last_tag(&package_root, &tag_glob, lightweight)
                 }
                 Err(err) => {
                     log::debug!("failed to find tag with glob `{}`: {}", tag_glob, err);
@@ -174,6 +256,37 @@ impl PackageRelease {
                 }
             }
         };
+        let prior_tag = match config.version_source() {
+            config::VersionSource::Manifest => {
+                let initial_tag = render_tag(
+                    tag_name,
+                    tag_prefix,
+                    name,
+                    &initial_version,
+                    &initial_version,
+                );
+                if git::tag_exists(&package_root, &initial_tag)? {
+                    Some(initial_tag)
+                } else {
+                    find_last_tag(false)
+                }
+            }
+            config::VersionSource::Tag => {
+                // Tags are the only record of the version, so take whatever kind was made
+                let prior_tag = find_last_tag(true);
+                if let Some(prior_tag) = prior_tag.as_deref() {
+                    let version = parse_tag_version(tag_name, tag_prefix, name, prior_tag)
+                        .ok_or_else(|| {
+                            anyhow::format_err!(
+                                "could not find the version of `{name}` in tag `{prior_tag}`"
+                            )
+                        })?;
+                    log::debug!("found version {version} of `{name}` from tag `{prior_tag}`");
+                    initial_version = Version::from(version);
+                }
+                prior_tag
+            }
+        };
 
         let planned_version = None;
         let planned_tag = None;
@@ -201,15 +314,29 @@ impl PackageRelease {
         Ok(pkg)
     }
 
+    /// Whether the version only lives in tags, per `version-source = "tag"`
+    pub fn versioned_by_tag(&self) -> bool {
+        self.config.version_source() == config::VersionSource::Tag
+    }
+
+    /// Whether a tag-versioned package has no tag to replace its placeholder manifest version
+    pub fn missing_tag_version(&self) -> bool {
+        self.versioned_by_tag()
+            && self.prior_tag.is_none()
+            && self.initial_version.bare_version == semver::Version::new(0, 0, 0)
+    }
+
     pub fn set_prior_tag(&mut self, prior_tag: String) {
         self.prior_tag = Some(prior_tag);
     }
 
-    pub fn bump<'s>(
-        &'s mut self,
+    pub fn bump(
+        &mut self,
+        ws_meta: &cargo_metadata::Metadata,
         level_or_version: &super::TargetVersion,
-        mut metadata: Option<&'s str>,
+        metadata: Option<&str>,
     ) -> CargoResult<()> {
+        let mut metadata = metadata.map(|m| m.to_owned());
         match self.config.metadata() {
             config::MetadataPolicy::Optional => {}
             config::MetadataPolicy::Required => {
@@ -229,15 +356,113 @@ impl PackageRelease {
             config::MetadataPolicy::Persistent => {
                 let initial_metadata = &self.initial_version.full_version.build;
                 if !initial_metadata.is_empty() {
-                    metadata.get_or_insert(initial_metadata.as_str());
+                    metadata.get_or_insert_with(|| initial_metadata.to_string());
                 }
             }
+            config::MetadataPolicy::Auto => {
+                metadata.get_or_insert_with(|| AUTO_METADATA.to_owned());
+            }
+        }
+        if metadata.as_deref() == Some(AUTO_METADATA) {
+            metadata = Some(self.auto_metadata()?);
+        }
+        let metadata = metadata.as_deref();
+        let prerelease_channels = self.config.prerelease_channels().collect::<Vec<_>>();
+        if let super::TargetVersion::Relative(super::BumpLevel::Auto) = level_or_version {
+            let Some(level) = self.auto_level(ws_meta)? else {
+                log::debug!("no changes call for a new version of `{}`", self.meta.name);
+                self.planned_version = None;
+                return Ok(());
+            };
+            log::debug!("bumping `{}` by `{}`", self.meta.name, level);
+            self.planned_version = self
+                .version_scheme_target(&super::TargetVersion::Relative(level))
+                .bump(
+                    &self.initial_version.full_version,
+                    metadata,
+                    &prerelease_channels,
+                )?;
+            return Ok(());
         }
-        self.planned_version =
-            level_or_version.bump(&self.initial_version.full_version, metadata)?;
+        self.planned_version = self.version_scheme_target(level_or_version).bump(
+            &self.initial_version.full_version,
+            metadata,
+            &prerelease_channels,
+        )?;
         Ok(())
     }
 
+    /// Render `metadata-template` for `HEAD`
+    fn auto_metadata(&self) -> CargoResult<String> {
+        let head = git::head_commit(&self.package_root)?;
+        let sha = head.id.to_string();
+        let commit_date = time::OffsetDateTime::from_unix_timestamp(head.time.seconds())?
+            .format(time::macros::format_description!("[year]-[month]-[day]"))?;
+        let template = Template {
+            crate_name: Some(self.meta.name.as_str()),
+            date: Some(NOW.as_str()),
+            sha: Some(&sha),
+            short_sha: Some(&head.short_id),
+            commit_date: Some(&commit_date),
+            ..Default::default()
+        };
+        render_metadata(self.config.metadata_template(), &template)
+    }
+
+    /// Map semver levels to the package's `version-scheme`
+    fn version_scheme_target(&self, target: &super::TargetVersion) -> super::TargetVersion {
+        match (self.config.version_scheme(), target) {
+            (
+                config::VersionScheme::Calver,
+                super::TargetVersion::Relative(
+                    super::BumpLevel::Major | super::BumpLevel::Minor | super::BumpLevel::Patch,
+                ),
+            ) => super::TargetVersion::Calendar,
+            _ => target.clone(),
+        }
+    }
+
+    /// Tag the current version was, or will be, released under
+    pub fn initial_tag(&self) -> Option<String> {
+        self.config.tag().then(|| {
+            render_tag(
+                self.config.tag_name(),
+                self.config.tag_prefix(self.is_root),
+                self.meta.name.as_str(),
+                &self.initial_version,
+                &self.initial_version,
+            )
+        })
+    }
+
+    /// Level suggested by the commits since the prior tag
+    fn auto_level(
+        &self,
+        ws_meta: &cargo_metadata::Metadata,
+    ) -> CargoResult<Option<super::BumpLevel>> {
+        let Some(prior_tag_name) = self.prior_tag.as_deref() else {
+            log::debug!(
+                "cannot detect changes for `{}` because no tag was found",
+                self.meta.name
+            );
+            return Ok(None);
+        };
+        // Whether the current version was released, rather than already bumped
+        let unbumped = if let Some(tag) = self.initial_tag() {
+            git::tag_exists(ws_meta.workspace_root.as_std_path(), &tag)?
+        } else {
+            false
+        };
+        let commits = super::changes::collect_commits(ws_meta, self, prior_tag_name)?;
+        let manifest_changes = super::changes::manifest_changes(ws_meta, self, prior_tag_name)?;
+        Ok(super::changes::suggest_level(
+            &commits,
+            &manifest_changes.breaking,
+            &self.initial_version.full_version,
+            !unbumped,
+        ))
+    }
+
     pub fn plan(&mut self) -> CargoResult<()> {
         if !self.config.release() {
             return Ok(());
@@ -268,6 +493,43 @@ impl PackageRelease {
     }
 }
 
+/// Apply `changes-include` / `changes-exclude` to the files checked for changes
+fn filter_changes(
+    package_content: &mut Vec<PathBuf>,
+    package_root: &Path,
+    config: &config::Config,
+) -> CargoResult<()> {
+    fn build(globs: &[String]) -> CargoResult<Option<globset::GlobSet>> {
+        if globs.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = globset::GlobSetBuilder::new();
+        for glob in globs {
+            builder.add(globset::Glob::new(glob)?);
+        }
+        Ok(Some(builder.build()?))
+    }
+    let include = build(config.changes_include())?;
+    let exclude = build(config.changes_exclude())?;
+
+    package_content.retain(|p| {
+        // Files outside of the package, like a workspace `Cargo.lock`, aren't filtered
+        let Ok(relpath) = p.strip_prefix(package_root) else {
+            return true;
+        };
+        include
+            .as_ref()
+            .map(|g| g.is_match(relpath))
+            .unwrap_or(true)
+            && !exclude
+                .as_ref()
+                .map(|g| g.is_match(relpath))
+                .unwrap_or(false)
+    });
+
+    Ok(())
+}
+
 fn render_tag(
     tag_name: &str,
     tag_prefix: &str,
@@ -312,6 +574,44 @@ fn render_tag_glob(tag_name: &str, tag_prefix: &str, name: &str) -> String {
     template.render(tag_name)
 }
 
+/// Reverse of [`render_tag`], for tags whose version is only recorded in the tag
+fn parse_tag_version(
+    tag_name: &str,
+    tag_prefix: &str,
+    name: &str,
+    tag: &str,
+) -> Option<semver::Version> {
+    const VERSION_VAR: &str = "\0";
+    let mut template = Template {
+        prev_version: Some("*"),
+        prev_metadata: Some("*"),
+        version: Some(VERSION_VAR),
+        metadata: Some("*"),
+        crate_name: Some(name),
+        ..Default::default()
+    };
+    let tag_prefix = template.render(tag_prefix);
+    template.prefix = Some(&tag_prefix);
+    let pattern = template
+        .render(tag_name)
+        .split(VERSION_VAR)
+        .map(|part| part.split('*').map(regex::escape).join(".*"))
+        .join("(?P<version>.+?)");
+    let captures = regex::Regex::new(&format!("^{pattern}$"))
+        .ok()?
+        .captures(tag)?;
+    semver::Version::parse(captures.name("version")?.as_str()).ok()
+}
+
+/// Render `metadata-template`, erroring if the result is not valid build metadata
+fn render_metadata(metadata_template: &str, template: &Template<'_>) -> CargoResult<String> {
+    let metadata = template.render(metadata_template);
+    semver::BuildMetadata::new(&metadata).map_err(|err| {
+        anyhow::format_err!("`metadata-template` rendered invalid metadata `{metadata}`: {err}")
+    })?;
+    Ok(metadata)
+}
+
 fn find_dependents<'w>(
     ws_meta: &'w cargo_metadata::Metadata,
     pkg_meta: &'w cargo_metadata::Package,
@@ -328,7 +628,188 @@ fn find_dependents<'w>(
     })
 }
 
-#[derive(Debug)]
+/// Whether `dependent` marks its dependency on `name` as `public = true`
+fn is_public_dependency(dependent: &cargo_metadata::Package, name: &str) -> CargoResult<bool> {
+    let manifest = std::fs::read_to_string(&dependent.manifest_path)?;
+    let api = crate::ops::manifest::ManifestApi::parse(&manifest, None)?;
+    Ok(api.dependency(name).is_some_and(|d| d.public))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum PlanFormat {
+    /// A list of planned releases
+    Json,
+    /// A plan file for `cargo release apply`
+    Toml,
+}
+
+/// A saved release plan, for `cargo release apply`
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ReleasePlan {
+    /// Commit the plan was made at
+    pub head: String,
+    pub package: Vec<PlannedRelease>,
+}
+
+impl ReleasePlan {
+    /// Plan `pkgs` at the current `HEAD`
+    pub fn new(ws_meta: &cargo_metadata::Metadata, pkgs: &[PackageRelease]) -> CargoResult<Self> {
+        let workspace_root = ws_meta.workspace_root.as_std_path();
+        let head = git::head_commit(workspace_root)?.id;
+        let package = pkgs
+            .iter()
+            .map(|pkg| PlannedRelease::new(pkg, workspace_root))
+            .collect::<CargoResult<Vec<_>>>()?;
+        Ok(Self {
+            head: head.to_string(),
+            package,
+        })
+    }
+
+    pub fn load(path: &Path) -> CargoResult<Self> {
+        let plan = std::fs::read_to_string(path)
+            .map_err(|err| anyhow::format_err!("failed to read {}: {err}", path.display()))?;
+        let plan = toml::from_str(&plan)
+            .map_err(|err| anyhow::format_err!("failed to parse {}: {err}", path.display()))?;
+        Ok(plan)
+    }
+
+    /// Error if `HEAD` is not based on the commit the plan was made at
+    pub fn verify_head(&self, dir: &Path) -> CargoResult<()> {
+        if !git::head_descends_from(dir, &self.head)? {
+            anyhow::bail!("HEAD is not based on {}, the commit planned at", self.head);
+        }
+        Ok(())
+    }
+
+    /// Use the planned version, tags, and publish settings for `pkg`, excluding it if it is not
+    /// in the plan
+    pub fn apply(&self, pkg: &mut PackageRelease) -> CargoResult<()> {
+        let crate_name = pkg.meta.name.as_str();
+        let Some(planned) = self.package.iter().find(|p| p.name == crate_name) else {
+            log::debug!("not in plan, skipping {crate_name}");
+            pkg.config.release = Some(false);
+            return Ok(());
+        };
+        let manifest_id = git::hash_file(&pkg.manifest_path)?.to_string();
+        if manifest_id != planned.manifest_id
+            || pkg.initial_version.full_version_string != planned.initial_version
+        {
+            anyhow::bail!("{crate_name}'s manifest changed since it was planned");
+        }
+        pkg.planned_version = planned
+            .planned_version
+            .as_deref()
+            .map(|v| semver::Version::parse(v).map(Version::from))
+            .transpose()?;
+        pkg.prior_tag = planned.prior_tag.clone();
+        planned.apply_config(pkg);
+        Ok(())
+    }
+
+    /// Error if `pkg` will not be tagged as planned
+    pub fn verify_tag(&self, pkg: &PackageRelease) -> CargoResult<()> {
+        let crate_name = pkg.meta.name.as_str();
+        let planned = self.package.iter().find(|p| p.name == crate_name);
+        if let Some(planned) = planned {
+            if planned.planned_tag != pkg.planned_tag {
+                anyhow::bail!(
+                    "{crate_name} would be tagged {}, rather than the planned {}",
+                    pkg.planned_tag.as_deref().unwrap_or("(none)"),
+                    planned.planned_tag.as_deref().unwrap_or("(none)")
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The decisions made for a [`PackageRelease`], for reporting
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlannedRelease {
+    pub name: String,
+    /// Relative to the workspace root
+    pub manifest_path: PathBuf,
+    /// Blob id of the manifest, to detect changes after planning
+    pub manifest_id: String,
+    pub initial_version: String,
+    pub planned_version: Option<String>,
+    pub prior_tag: Option<String>,
+    pub planned_tag: Option<String>,
+    pub publish: bool,
+    pub push: bool,
+    pub tag: bool,
+    pub registry: Option<String>,
+    pub features: Vec<String>,
+    pub all_features: bool,
+    pub dependents: Vec<String>,
+}
+
+impl PlannedRelease {
+    pub fn new(pkg: &PackageRelease, workspace_root: &Path) -> CargoResult<Self> {
+        let (features, all_features) = match &pkg.features {
+            cargo::Features::None => (Vec::new(), false),
+            cargo::Features::Selective(features) => (features.clone(), false),
+            cargo::Features::All => (Vec::new(), true),
+        };
+        Ok(Self {
+            name: pkg.meta.name.clone(),
+            manifest_path: pkg
+                .manifest_path
+                .strip_prefix(workspace_root)
+                .unwrap_or(&pkg.manifest_path)
+                .to_owned(),
+            manifest_id: git::hash_file(&pkg.manifest_path)?.to_string(),
+            initial_version: pkg.initial_version.full_version_string.clone(),
+            planned_version: pkg
+                .planned_version
+                .as_ref()
+                .map(|v| v.full_version_string.clone()),
+            prior_tag: pkg.prior_tag.clone(),
+            planned_tag: pkg.planned_tag.clone(),
+            publish: pkg.config.publish(),
+            push: pkg.config.push(),
+            tag: pkg.config.tag(),
+            registry: pkg.config.registry().map(|r| r.to_owned()),
+            features,
+            all_features,
+            dependents: pkg.dependents.iter().map(|d| d.pkg.name.clone()).collect(),
+        })
+    }
+
+    /// Use the planned publish, push, tag, and feature settings for `pkg`
+    pub fn apply_config(&self, pkg: &mut PackageRelease) {
+        pkg.config.publish = Some(self.publish);
+        pkg.config.push = Some(self.push);
+        pkg.config.tag = Some(self.tag);
+        pkg.config.registry = self.registry.clone();
+        pkg.config.enable_features = Some(self.features.clone());
+        pkg.config.enable_all_features = Some(self.all_features);
+        pkg.features = pkg.config.features();
+    }
+}
+
+/// Write the plan for `pkgs` to stdout
+pub fn write_plan(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[PackageRelease],
+    format: PlanFormat,
+) -> CargoResult<()> {
+    let plan = ReleasePlan::new(ws_meta, pkgs)?;
+    let output = match format {
+        PlanFormat::Json => {
+            let mut output = serde_json::to_string_pretty(&plan.package)?;
+            output.push('\n');
+            output
+        }
+        PlanFormat::Toml => toml::to_string_pretty(&plan)?,
+    };
+    std::io::Write::write_all(&mut std::io::stdout(), output.as_bytes())?;
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
 pub struct Dependency {
     pub pkg: cargo_metadata::Package,
     pub req: semver::VersionReq,
@@ -362,3 +843,189 @@ impl From<semver::Version> for Version {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod parse_tag_version {
+        use super::*;
+
+        #[test]
+        fn default_tag_name() {
+            let version = |tag| parse_tag_version("{{prefix}}v{{version}}", "", "foo", tag);
+            assert_eq!(version("v1.2.3"), Some(semver::Version::new(1, 2, 3)));
+            assert_eq!(
+                version("v1.0.0-rc.1"),
+                Some(semver::Version::parse("1.0.0-rc.1").unwrap())
+            );
+            assert_eq!(version("1.2.3"), None);
+            assert_eq!(version("vnext"), None);
+        }
+
+        #[test]
+        fn prefixed() {
+            let version =
+                |tag| parse_tag_version("{{prefix}}v{{version}}", "{{crate_name}}-", "foo", tag);
+            assert_eq!(version("foo-v0.4.1"), Some(semver::Version::new(0, 4, 1)));
+            assert_eq!(version("bar-v0.4.1"), None);
+        }
+    }
+
+    mod filter_changes {
+        use super::*;
+
+        fn filter(include: &[&str], exclude: &[&str]) -> Vec<PathBuf> {
+            let config = config::Config {
+                changes_include: Some(include.iter().map(|g| (*g).to_owned()).collect()),
+                changes_exclude: Some(exclude.iter().map(|g| (*g).to_owned()).collect()),
+                ..Default::default()
+            };
+            let mut content = vec![
+                PathBuf::from("/ws/foo/Cargo.toml"),
+                PathBuf::from("/ws/foo/README.md"),
+                PathBuf::from("/ws/foo/src/lib.rs"),
+                PathBuf::from("/ws/foo/benches/bench.rs"),
+                PathBuf::from("/ws/Cargo.lock"),
+            ];
+            filter_changes(&mut content, Path::new("/ws/foo"), &config).unwrap();
+            content
+        }
+
+        #[test]
+        fn unfiltered() {
+            assert_eq!(filter(&[], &[]).len(), 5);
+        }
+
+        #[test]
+        fn include() {
+            assert_eq!(
+                filter(&["src/**", "Cargo.toml"], &[]),
+                [
+                    PathBuf::from("/ws/foo/Cargo.toml"),
+                    PathBuf::from("/ws/foo/src/lib.rs"),
+                    PathBuf::from("/ws/Cargo.lock"),
+                ]
+            );
+        }
+
+        #[test]
+        fn exclude() {
+            assert_eq!(
+                filter(&[], &["benches/**", "README.md"]),
+                [
+                    PathBuf::from("/ws/foo/Cargo.toml"),
+                    PathBuf::from("/ws/foo/src/lib.rs"),
+                    PathBuf::from("/ws/Cargo.lock"),
+                ]
+            );
+        }
+
+        #[test]
+        fn exclude_overrides_include() {
+            assert_eq!(
+                filter(&["src/**", "README.md"], &["*.md"]),
+                [
+                    PathBuf::from("/ws/foo/src/lib.rs"),
+                    PathBuf::from("/ws/Cargo.lock"),
+                ]
+            );
+        }
+
+        #[test]
+        fn outside_package_root() {
+            // Globs are relative to the package root, so this doesn't match the workspace lock
+            assert_eq!(
+                filter(&[], &["Cargo.lock", "**/Cargo.lock"]),
+                [
+                    PathBuf::from("/ws/foo/Cargo.toml"),
+                    PathBuf::from("/ws/foo/README.md"),
+                    PathBuf::from("/ws/foo/src/lib.rs"),
+                    PathBuf::from("/ws/foo/benches/bench.rs"),
+                    PathBuf::from("/ws/Cargo.lock"),
+                ]
+            );
+        }
+
+        #[test]
+        fn invalid_glob() {
+            let config = config::Config {
+                changes_include: Some(vec!["src/[".to_owned()]),
+                ..Default::default()
+            };
+            let mut content = vec![PathBuf::from("/ws/foo/src/lib.rs")];
+            assert!(filter_changes(&mut content, Path::new("/ws/foo"), &config).is_err());
+        }
+    }
+
+    mod render_metadata {
+        use super::*;
+
+        const TEMPLATE: Template<'_> = Template {
+            prev_version: None,
+            prev_metadata: None,
+            version: None,
+            metadata: None,
+            crate_name: Some("foo"),
+            date: Some("2024-03-15"),
+            sha: Some("ef895fda3e5ef8d617ce5776264588a56ab583fa"),
+            short_sha: Some("ef895fd"),
+            commit_date: Some("2024-03-14"),
+            prefix: None,
+            tag_name: None,
+            contributors: None,
+            references: None,
+        };
+
+        #[test]
+        fn default_template() {
+            let config = config::Config::default();
+            let actual = render_metadata(config.metadata_template(), &TEMPLATE).unwrap();
+            assert_eq!(actual, "ef895fd");
+        }
+
+        #[test]
+        fn variables() {
+            let actual = render_metadata("git.{{commit_date}}.{{sha}}", &TEMPLATE).unwrap();
+            assert_eq!(
+                actual,
+                "git.2024-03-14.ef895fda3e5ef8d617ce5776264588a56ab583fa"
+            );
+        }
+
+        #[test]
+        fn invalid() {
+            assert!(render_metadata("{{crate_name}}_{{short_sha}}", &TEMPLATE).is_err());
+            assert!(render_metadata("git..{{short_sha}}", &TEMPLATE).is_err());
+        }
+    }
+
+    mod release_plan {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let plan = ReleasePlan {
+                head: "ef895fda3e5ef8d617ce5776264588a56ab583fa".to_owned(),
+                package: vec![PlannedRelease {
+                    name: "foo".to_owned(),
+                    manifest_path: PathBuf::from("foo/Cargo.toml"),
+                    manifest_id: "d39c2090c1fa3cb645220e3a31d7f8d75815c5ca".to_owned(),
+                    initial_version: "1.2.3".to_owned(),
+                    planned_version: Some("1.3.0".to_owned()),
+                    prior_tag: Some("foo-v1.2.3".to_owned()),
+                    planned_tag: Some("foo-v1.3.0".to_owned()),
+                    publish: true,
+                    push: true,
+                    tag: true,
+                    registry: None,
+                    features: vec!["std".to_owned()],
+                    all_features: false,
+                    dependents: vec!["bar".to_owned()],
+                }],
+            };
+            let actual: ReleasePlan = toml::from_str(&toml::to_string(&plan).unwrap()).unwrap();
+            assert_eq!(actual, plan);
+        }
+    }
+}
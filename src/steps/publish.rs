@@ -1,5 +1,6 @@
 use itertools::Itertools;
 
+use crate::error::CargoResult;
 use crate::error::CliError;
 use crate::ops::git;
 use crate::steps::plan;
@@ -152,7 +153,13 @@ impl PublishStep {
         super::confirm("Publish", &selected_pkgs, self.no_confirm, dry_run)?;
 
         // STEP 3: cargo publish
-        publish(&selected_pkgs, dry_run, &ws_config.unstable)?;
+        publish(
+            &ws_meta,
+            &selected_pkgs,
+            dry_run,
+            &ws_config.unstable,
+            &mut |_| Ok(()),
+        )?;
 
         super::finish(failed, dry_run)
     }
@@ -170,9 +177,69 @@ impl PublishStep {
 }
 
 pub fn publish(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    unstable: &crate::config::Unstable,
+    on_published: &mut dyn FnMut(&plan::PackageRelease) -> CargoResult<()>,
+) -> Result<(), CliError> {
+    if dry_run {
+        // Dry-runs are always `--allow-dirty`, so there is nothing to keep apart
+        return publish_packages(pkgs, dry_run, true, unstable, on_published);
+    }
+
+    // Only tag-versioned packages need `--allow-dirty`, so keep them apart from the rest
+    for (versioned_by_tag, run) in &pkgs.iter().chunk_by(|p| p.versioned_by_tag()) {
+        let run = run.cloned().collect::<Vec<_>>();
+        if versioned_by_tag {
+            publish_versioned_by_tag(ws_meta, &run, dry_run, unstable, on_published)?;
+        } else {
+            publish_packages(&run, dry_run, false, unstable, on_published)?;
+        }
+    }
+    Ok(())
+}
+
+/// Publish with the version temporarily written to the manifest, restoring the files afterwards
+fn publish_versioned_by_tag(
+    ws_meta: &cargo_metadata::Metadata,
     pkgs: &[plan::PackageRelease],
     dry_run: bool,
     unstable: &crate::config::Unstable,
+    on_published: &mut dyn FnMut(&plan::PackageRelease) -> CargoResult<()>,
+) -> Result<(), CliError> {
+    let mut originals = Vec::new();
+    let lock_path = ws_meta.workspace_root.as_std_path().join("Cargo.lock");
+    if let Ok(lock) = std::fs::read_to_string(&lock_path) {
+        originals.push((lock_path, lock));
+    }
+    for pkg in pkgs.iter().filter(|p| p.config.publish()) {
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        let manifest = std::fs::read_to_string(&pkg.manifest_path)?;
+        originals.push((pkg.manifest_path.clone(), manifest));
+        crate::ops::cargo::set_package_version(
+            &pkg.manifest_path,
+            &version.full_version_string,
+            dry_run,
+        )?;
+    }
+
+    let result = publish_packages(pkgs, dry_run, true, unstable, on_published);
+
+    if !dry_run {
+        for (path, original) in originals {
+            std::fs::write(path, original)?;
+        }
+    }
+    result
+}
+
+fn publish_packages(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    allow_dirty: bool,
+    unstable: &crate::config::Unstable,
+    on_published: &mut dyn FnMut(&plan::PackageRelease) -> CargoResult<()>,
 ) -> Result<(), CliError> {
     if pkgs.is_empty() {
         Ok(())
@@ -185,12 +252,20 @@ pub fn publish(
             .all(|p| p.config.registry() == registry && p.config.target.as_deref() == target)
         {
             let manifest_path = &first_pkg.manifest_path;
-            workspace_publish(manifest_path, pkgs, registry, target, dry_run)
+            workspace_publish(
+                manifest_path,
+                pkgs,
+                registry,
+                target,
+                dry_run,
+                allow_dirty,
+                on_published,
+            )
         } else {
-            serial_publish(pkgs, dry_run)
+            serial_publish(pkgs, dry_run, allow_dirty, on_published)
         }
     } else {
-        serial_publish(pkgs, dry_run)
+        serial_publish(pkgs, dry_run, allow_dirty, on_published)
     }
 }
 
@@ -200,6 +275,8 @@ fn workspace_publish(
     registry: Option<&str>,
     target: Option<&str>,
     dry_run: bool,
+    allow_dirty: bool,
+    on_published: &mut dyn FnMut(&plan::PackageRelease) -> CargoResult<()>,
 ) -> Result<(), CliError> {
     let crate_names = pkgs.iter().map(|p| p.meta.name.as_str()).join(", ");
     let _ = crate::ops::shell::status("Publishing", crate_names);
@@ -218,6 +295,7 @@ fn workspace_publish(
         .collect::<Vec<_>>();
     if !crate::ops::cargo::publish(
         dry_run,
+        allow_dirty,
         verify,
         manifest_path,
         &pkgids,
@@ -225,8 +303,12 @@ fn workspace_publish(
         registry,
         target,
     )? {
+        // Which packages made it is unknown, so `--resume` looks them up in the index
         return Err(101.into());
     }
+    for pkg in pkgs.iter().filter(|p| p.config.publish()) {
+        on_published(pkg)?;
+    }
 
     // HACK: This is a fallback in case users can't or don't want to rely on cargo waiting for
     // them
@@ -248,7 +330,12 @@ fn workspace_publish(
     Ok(())
 }
 
-fn serial_publish(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError> {
+fn serial_publish(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    allow_dirty: bool,
+    on_published: &mut dyn FnMut(&plan::PackageRelease) -> CargoResult<()>,
+) -> Result<(), CliError> {
     for pkg in pkgs {
         if !pkg.config.publish() {
             continue;
@@ -275,6 +362,7 @@ fn serial_publish(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), Cl
         let pkgid = &[crate_name];
         if !crate::ops::cargo::publish(
             dry_run,
+            allow_dirty,
             verify,
             &pkg.manifest_path,
             pkgid,
@@ -284,6 +372,7 @@ fn serial_publish(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), Cl
         )? {
             return Err(101.into());
         }
+        on_published(pkg)?;
 
         // HACK: This is a fallback in case users can't or don't want to rely on cargo waiting for
         // them
@@ -2,9 +2,10 @@ use crate::config;
 use crate::error::CliError;
 use crate::ops::cargo;
 use crate::ops::git;
+use crate::steps::journal;
 use crate::steps::plan;
 
-#[derive(Debug, Clone, clap::Args)]
+#[derive(Debug, Clone, Default, clap::Args)]
 pub struct ReleaseStep {
     #[command(flatten)]
     manifest: clap_cargo::Manifest,
@@ -16,11 +17,14 @@ pub struct ReleaseStep {
     #[arg(long, conflicts_with = "level_or_version")]
     unpublished: bool,
 
+    #[command(flatten)]
+    changed: super::ChangedArgs,
+
     /// Either bump by LEVEL or set the VERSION for all selected packages
     #[arg(value_name = "LEVEL|VERSION")]
     level_or_version: Option<super::TargetVersion>,
 
-    /// Semver metadata
+    /// Semver metadata, or `auto` to derive it from git
     #[arg(short, long, requires = "level_or_version")]
     metadata: Option<String>,
 
@@ -39,11 +43,44 @@ pub struct ReleaseStep {
     #[arg(long, value_name = "NAME")]
     prev_tag_name: Option<String>,
 
+    /// Write the release plan to stdout in FORMAT, without releasing
+    #[arg(long, value_name = "FORMAT", value_enum, conflicts_with = "execute")]
+    plan_format: Option<plan::PlanFormat>,
+
+    /// Continue an executed release that failed partway, skipping completed steps
+    #[arg(
+        long,
+        conflicts_with_all = ["level_or_version", "unpublished", "changed_selection", "plan_format"]
+    )]
+    resume: bool,
+
     #[command(flatten)]
     config: config::ConfigArgs,
+
+    /// Saved plan to release, in place of selecting and bumping packages
+    #[arg(skip)]
+    plan: Option<std::path::PathBuf>,
 }
 
 impl ReleaseStep {
+    /// Release exactly what `plan` describes
+    pub fn from_plan(
+        plan: std::path::PathBuf,
+        manifest: clap_cargo::Manifest,
+        config: config::ConfigArgs,
+        execute: bool,
+        no_confirm: bool,
+    ) -> Self {
+        Self {
+            manifest,
+            execute,
+            no_confirm,
+            config,
+            plan: Some(plan),
+            ..Default::default()
+        }
+    }
+
     pub fn run(&self) -> Result<(), CliError> {
         git::git_version()?;
         let mut index = crate::ops::index::CratesIoIndex::new();
@@ -61,6 +98,22 @@ impl ReleaseStep {
             .exec()?;
         let mut ws_config = config::load_workspace_config(&self.config, &ws_meta)?;
         let mut pkgs = plan::load(&self.config, &ws_meta)?;
+        let release_plan = self
+            .plan
+            .as_deref()
+            .map(plan::ReleasePlan::load)
+            .transpose()?;
+        if let Some(release_plan) = &release_plan {
+            release_plan.verify_head(ws_meta.workspace_root.as_std_path())?;
+        }
+        let journal_path = journal::Journal::path(&ws_meta);
+        let resumed = self
+            .resume
+            .then(|| journal::Journal::load(&journal_path))
+            .transpose()?;
+        if let Some(resumed) = &resumed {
+            resumed.verify_head(ws_meta.workspace_root.as_std_path())?;
+        }
 
         for pkg in pkgs.values_mut() {
             if let Some(prev_tag) = self.prev_tag_name.as_ref() {
@@ -68,9 +121,13 @@ impl ReleaseStep {
                 // they don't care about any changes from before this tag.
                 pkg.set_prior_tag(prev_tag.to_owned());
             }
-            if pkg.config.release() {
+            if let Some(resumed) = &resumed {
+                resumed.resume(pkg)?;
+            } else if let Some(release_plan) = &release_plan {
+                release_plan.apply(pkg)?;
+            } else if pkg.config.release() {
                 if let Some(level_or_version) = &self.level_or_version {
-                    pkg.bump(level_or_version, self.metadata.as_deref())?;
+                    pkg.bump(&ws_meta, level_or_version, self.metadata.as_deref())?;
                 }
             }
             if index.has_krate(
@@ -83,7 +140,14 @@ impl ReleaseStep {
             }
         }
 
-        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        let (_selected_pkgs, excluded_pkgs) = if release_plan.is_some() || resumed.is_some() {
+            // The plan or journal already selected the packages
+            Default::default()
+        } else {
+            self.changed
+                .partition_packages(&self.workspace, &ws_meta, &pkgs)?
+        };
+        let mut deselected = std::collections::HashSet::new();
         for excluded_pkg in &excluded_pkgs {
             let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) else {
                 // Either not in workspace or marked as `release = false`.
@@ -121,6 +185,9 @@ impl ReleaseStep {
 
             pkg.planned_version = None;
             pkg.config.release = Some(false);
+            if !explicitly_excluded {
+                deselected.insert(excluded_pkg.id.clone());
+            }
 
             if let Some(prior_tag_name) = &pkg.prior_tag {
                 if let Some(changed) =
@@ -149,13 +216,38 @@ impl ReleaseStep {
             }
         }
 
+        if release_plan.is_none() && resumed.is_none() {
+            plan::cascade_bumps(&ws_meta, &mut pkgs, &deselected, self.metadata.as_deref())?;
+        }
+
+        if matches!(
+            self.level_or_version,
+            Some(super::TargetVersion::Relative(super::BumpLevel::Auto))
+        ) {
+            for pkg in pkgs.values_mut() {
+                if pkg.planned_version.is_none() && pkg.config.shared_version().is_none() {
+                    // Nothing changed, so there is nothing to release
+                    pkg.config.release = Some(false);
+                }
+            }
+        }
+
         let pkgs = plan::plan(pkgs)?;
+        if let Some(release_plan) = release_plan.as_ref().or(resumed.as_ref().map(|j| &j.plan)) {
+            for pkg in pkgs.values().filter(|p| p.config.release()) {
+                release_plan.verify_tag(pkg)?;
+            }
+        }
 
         for excluded_pkg in &excluded_pkgs {
             let Some(pkg) = pkgs.get(&excluded_pkg.id) else {
                 // Either not in workspace or marked as `release = false`.
                 continue;
             };
+            if pkg.config.release() {
+                // Pulled back in by `cascade-bumps`
+                continue;
+            }
 
             // HACK: `index` only supports default registry
             if pkg.config.publish() && pkg.config.registry().is_none() {
@@ -185,26 +277,96 @@ impl ReleaseStep {
             return Err(2.into());
         }
 
+        if let Some(format) = self.plan_format {
+            plan::write_plan(&ws_meta, &selected_pkgs, format)?;
+            return Ok(());
+        }
+
         let dry_run = !self.execute;
         let mut failed = false;
 
+        // Plans and journals carry the versions chosen when they were made
+        let absolute_version = release_plan.is_some()
+            || resumed.is_some()
+            || matches!(
+                self.level_or_version,
+                Some(super::TargetVersion::Absolute(_))
+            );
+
+        let mut journal = match resumed {
+            Some(mut journal) => {
+                journal.verify_published(&selected_pkgs, &mut |pkg| {
+                    let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+                    cargo::is_published(
+                        &mut index,
+                        pkg.config.registry(),
+                        &pkg.meta.name,
+                        &version.full_version_string,
+                        pkg.config.certs_source(),
+                    )
+                });
+                journal.verify_tags(&selected_pkgs)?;
+                journal
+            }
+            None => journal::Journal::new(plan::ReleasePlan::new(&ws_meta, &selected_pkgs)?),
+        };
+        let to_publish = journal::Journal::pending(&selected_pkgs, &journal.published);
+        let to_own = journal::Journal::pending(&selected_pkgs, &journal.owners);
+        let to_tag = journal::Journal::pending(&selected_pkgs, &journal.tagged);
+
         let consolidate_commits = super::consolidate_commits(&selected_pkgs, &excluded_pkgs)?;
         ws_config.consolidate_commits = Some(consolidate_commits);
 
         // STEP 0: Help the user make the right decisions.
+        for pkg in selected_pkgs.iter().filter(|p| p.versioned_by_tag()) {
+            let skipped = [
+                ("changelog-update", pkg.config.changelog_update()),
+                (
+                    "pre-release-replacements",
+                    !pkg.config.pre_release_replacements().is_empty(),
+                ),
+                ("pre-release-hook", pkg.config.pre_release_hook().is_some()),
+            ]
+            .into_iter()
+            .filter_map(|(field, configured)| configured.then_some(field))
+            .collect::<Vec<_>>();
+            if !skipped.is_empty() {
+                let _ = crate::ops::shell::warn(format!(
+                    "{} is versioned by tag, skipping its {}",
+                    pkg.meta.name,
+                    skipped.join(", ")
+                ));
+            }
+        }
+
         failed |= !super::verify_git_is_clean(
             ws_meta.workspace_root.as_std_path(),
             dry_run,
             log::Level::Error,
         )?;
 
-        failed |= !super::verify_tags_missing(&selected_pkgs, dry_run, log::Level::Error)?;
+        failed |= !super::verify_tags_missing(&to_tag, dry_run, log::Level::Error)?;
+
+        failed |= !super::verify_tag_versions(
+            &selected_pkgs,
+            absolute_version,
+            dry_run,
+            log::Level::Error,
+        )?;
 
         failed |=
             !super::verify_monotonically_increasing(&selected_pkgs, dry_run, log::Level::Error)?;
 
+        failed |= !super::verify_dependent_versions(
+            &ws_meta,
+            &selected_pkgs,
+            &excluded_pkgs,
+            dry_run,
+            log::Level::Error,
+        )?;
+
         let mut double_publish = false;
-        for pkg in &selected_pkgs {
+        for pkg in &to_publish {
             if !pkg.config.publish() {
                 continue;
             }
@@ -233,6 +395,8 @@ impl ReleaseStep {
 
         super::warn_changed(&ws_meta, &selected_pkgs)?;
 
+        failed |= !super::verify_manifest_changes(&ws_meta, &selected_pkgs, dry_run)?;
+
         failed |= !super::verify_git_branch(
             ws_meta.workspace_root.as_std_path(),
             &ws_config,
@@ -259,8 +423,19 @@ impl ReleaseStep {
         // STEP 1: Release Confirmation
         super::confirm("Release", &selected_pkgs, self.no_confirm, dry_run)?;
 
+        let save_journal = |journal: &journal::Journal| {
+            if dry_run {
+                Ok(())
+            } else {
+                journal.save(&journal_path)
+            }
+        };
+        save_journal(&journal)?;
+
         // STEP 2: update current version, save and commit
-        if consolidate_commits {
+        if journal.commit.is_some() {
+            log::debug!("skipping version updates, already committed");
+        } else if consolidate_commits {
             let update_lock =
                 super::version::update_versions(&ws_meta, &selected_pkgs, &excluded_pkgs, dry_run)?;
             if update_lock {
@@ -272,15 +447,25 @@ impl ReleaseStep {
             }
 
             for pkg in &selected_pkgs {
+                if pkg.versioned_by_tag() {
+                    continue;
+                }
+                super::changes::update_changelog(&ws_meta, pkg, dry_run)?;
                 super::replace::replace(pkg, dry_run)?;
 
                 // pre-release hook
                 super::hook::hook(&ws_meta, pkg, dry_run)?;
             }
 
-            super::commit::workspace_commit(&ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+            // Tag-versioned packages have nothing to commit
+            if !selected_pkgs.iter().all(|p| p.versioned_by_tag()) {
+                super::commit::workspace_commit(&ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+            }
         } else {
             for pkg in &selected_pkgs {
+                if pkg.versioned_by_tag() || journal.committed.contains(&pkg.meta.name) {
+                    continue;
+                }
                 if let Some(version) = pkg.planned_version.as_ref() {
                     let crate_name = pkg.meta.name.as_str();
                     let _ = crate::ops::shell::status(
@@ -307,24 +492,58 @@ impl ReleaseStep {
                     }
                 }
 
+                super::changes::update_changelog(&ws_meta, pkg, dry_run)?;
                 super::replace::replace(pkg, dry_run)?;
 
                 // pre-release hook
                 super::hook::hook(&ws_meta, pkg, dry_run)?;
 
                 super::commit::pkg_commit(pkg, dry_run)?;
+                journal.committed.push(pkg.meta.name.clone());
+                save_journal(&journal)?;
             }
         }
 
+        if journal.commit.is_none() {
+            let head = git::head_commit(ws_meta.workspace_root.as_std_path())?;
+            journal.commit = Some(head.id.to_string());
+            save_journal(&journal)?;
+        }
+
         // STEP 3: cargo publish
-        super::publish::publish(&selected_pkgs, dry_run, &ws_config.unstable)?;
-        super::owner::ensure_owners(&selected_pkgs, dry_run)?;
+        super::publish::publish(
+            &ws_meta,
+            &to_publish,
+            dry_run,
+            &ws_config.unstable,
+            &mut |pkg| {
+                journal.published.push(pkg.meta.name.clone());
+                save_journal(&journal)
+            },
+        )?;
+        super::owner::ensure_owners(&to_own, dry_run)?;
+        journal.owners = selected_pkgs.iter().map(|p| p.meta.name.clone()).collect();
+        save_journal(&journal)?;
 
         // STEP 5: Tag
-        super::tag::tag(&selected_pkgs, dry_run)?;
+        super::tag::tag(&ws_meta, &to_tag, dry_run, &mut |pkg| {
+            journal.tagged.push(pkg.meta.name.clone());
+            save_journal(&journal)
+        })?;
 
         // STEP 6: git push
-        super::push::push(&ws_config, &ws_meta, &selected_pkgs, dry_run)?;
+        if journal.pushed {
+            log::debug!("skipping push, already pushed");
+        } else {
+            super::push::push(&ws_config, &ws_meta, &selected_pkgs, dry_run)?;
+            journal.pushed = true;
+            save_journal(&journal)?;
+        }
+
+        if !dry_run {
+            // Nothing left to resume
+            std::fs::remove_file(&journal_path)?;
+        }
 
         super::finish(failed, dry_run)
     }
@@ -101,6 +101,7 @@ impl ReplaceStep {
             }
 
             pkg.config.pre_release_replacements = Some(vec![]);
+            pkg.config.version_files = Some(vec![]);
             pkg.config.release = Some(false);
         }
 
@@ -194,6 +195,12 @@ pub fn replace(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliError
             dry_run,
         )?;
     }
+    crate::ops::version_file::update_version_files(
+        pkg.config.version_files(),
+        &version.bare_version_string,
+        &pkg.package_root,
+        dry_run,
+    )?;
 
     Ok(())
 }
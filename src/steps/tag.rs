@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use crate::error::CargoResult;
 use crate::error::CliError;
 use crate::ops::git;
 use crate::ops::replace::NOW;
@@ -120,6 +121,8 @@ impl TagStep {
             log::Level::Error,
         )?;
 
+        failed |= !super::verify_tag_versions(&selected_pkgs, false, dry_run, log::Level::Error)?;
+
         failed |= !super::verify_git_branch(
             ws_meta.workspace_root.as_std_path(),
             &ws_config,
@@ -138,7 +141,7 @@ impl TagStep {
         super::confirm("Tag", &selected_pkgs, self.no_confirm, dry_run)?;
 
         // STEP 5: Tag
-        tag(&selected_pkgs, dry_run)?;
+        tag(&ws_meta, &selected_pkgs, dry_run, &mut |_| Ok(()))?;
 
         super::finish(failed, dry_run)
     }
@@ -155,7 +158,12 @@ impl TagStep {
     }
 }
 
-pub fn tag(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError> {
+pub fn tag(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    on_tagged: &mut dyn FnMut(&plan::PackageRelease) -> CargoResult<()>,
+) -> Result<(), CliError> {
     let mut seen_tags = HashSet::new();
     for pkg in pkgs {
         if let Some(tag_name) = pkg.planned_tag.as_ref() {
@@ -168,6 +176,27 @@ pub fn tag(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError>
                 let prev_metadata_var = pkg.initial_version.full_version.build.as_str();
                 let version_var = version.bare_version_string.as_str();
                 let metadata_var = version.full_version.build.as_str();
+                let tag_message = pkg.config.tag_message();
+                let (contributors, references) = if tag_message.contains("{{contributors}}")
+                    || tag_message.contains("{{references}}")
+                {
+                    let commits = match pkg.prior_tag.as_deref() {
+                        Some(prior_tag_name) => {
+                            crate::steps::changes::collect_commits(ws_meta, pkg, prior_tag_name)?
+                        }
+                        None => Vec::new(),
+                    };
+                    (
+                        crate::steps::changes::contributors(&commits).join(", "),
+                        crate::steps::changes::references(&commits)
+                            .iter()
+                            .map(|r| r.id.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    )
+                } else {
+                    Default::default()
+                };
                 let template = Template {
                     prev_version: Some(prev_version_var),
                     prev_metadata: Some(prev_metadata_var),
@@ -176,9 +205,11 @@ pub fn tag(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError>
                     crate_name: Some(crate_name),
                     tag_name: Some(tag_name),
                     date: Some(NOW.as_str()),
+                    contributors: Some(&contributors),
+                    references: Some(&references),
                     ..Default::default()
                 };
-                let tag_message = template.render(pkg.config.tag_message());
+                let tag_message = template.render(tag_message);
 
                 log::debug!("creating git tag {}", tag_name);
                 if !git::tag(cwd, tag_name, &tag_message, pkg.config.sign_tag(), dry_run)? {
@@ -186,6 +217,7 @@ pub fn tag(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError>
                     return Err(101.into());
                 }
             }
+            on_tagged(pkg)?;
         }
     }
 
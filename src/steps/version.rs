@@ -1,3 +1,5 @@
+use itertools::Itertools as _;
+
 use crate::error::CargoResult;
 use crate::error::CliError;
 use crate::ops::git;
@@ -12,6 +14,9 @@ pub struct VersionStep {
     #[command(flatten)]
     workspace: clap_cargo::Workspace,
 
+    #[command(flatten)]
+    changed: super::ChangedArgs,
+
     /// Custom config file
     #[arg(short, long = "config")]
     custom_config: Option<std::path::PathBuf>,
@@ -40,10 +45,18 @@ pub struct VersionStep {
     no_confirm: bool,
 
     /// Either bump by LEVEL or set the VERSION for all selected packages
-    #[arg(value_name = "LEVEL|VERSION", help_heading = "Version")]
-    level_or_version: super::TargetVersion,
-
-    /// Semver metadata
+    #[arg(
+        value_name = "LEVEL|VERSION",
+        help_heading = "Version",
+        required_unless_present = "check"
+    )]
+    level_or_version: Option<super::TargetVersion>,
+
+    /// Verify versions are consistent, without changing anything
+    #[arg(long, conflicts_with_all = ["level_or_version", "execute"])]
+    check: bool,
+
+    /// Semver metadata, or `auto` to derive it from git
     #[arg(short, long, help_heading = "Version")]
     metadata: Option<String>,
 
@@ -71,6 +84,14 @@ impl VersionStep {
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
 
+        if self.check {
+            return self.check(&ws_meta, pkgs);
+        }
+        let level_or_version = self
+            .level_or_version
+            .as_ref()
+            .expect("clap requires it without `--check`");
+
         for pkg in pkgs.values_mut() {
             if let Some(prev_tag) = self.prev_tag_name.as_ref() {
                 // Trust the user that the tag passed in is the latest tag for the workspace and that
@@ -78,11 +99,14 @@ impl VersionStep {
                 pkg.set_prior_tag(prev_tag.to_owned());
             }
             if pkg.config.release() {
-                pkg.bump(&self.level_or_version, self.metadata.as_deref())?;
+                pkg.bump(&ws_meta, level_or_version, self.metadata.as_deref())?;
             }
         }
 
-        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        let (_selected_pkgs, excluded_pkgs) =
+            self.changed
+                .partition_packages(&self.workspace, &ws_meta, &pkgs)?;
+        let mut deselected = std::collections::HashSet::new();
         for excluded_pkg in excluded_pkgs {
             let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) else {
                 // Either not in workspace or marked as `release = false`.
@@ -94,8 +118,13 @@ impl VersionStep {
 
             pkg.planned_version = None;
             pkg.config.release = Some(false);
+            if !self.workspace.exclude.contains(&excluded_pkg.name) {
+                deselected.insert(excluded_pkg.id.clone());
+            }
         }
 
+        plan::cascade_bumps(&ws_meta, &mut pkgs, &deselected, self.metadata.as_deref())?;
+
         let pkgs = plan::plan(pkgs)?;
 
         let (selected_pkgs, excluded_pkgs): (Vec<_>, Vec<_>) = pkgs
@@ -120,8 +149,18 @@ impl VersionStep {
         failed |=
             !super::verify_monotonically_increasing(&selected_pkgs, dry_run, log::Level::Error)?;
 
+        failed |= !super::verify_dependent_versions(
+            &ws_meta,
+            &selected_pkgs,
+            &excluded_pkgs,
+            dry_run,
+            log::Level::Error,
+        )?;
+
         super::warn_changed(&ws_meta, &selected_pkgs)?;
 
+        failed |= !super::verify_manifest_changes(&ws_meta, &selected_pkgs, dry_run)?;
+
         failed |= !super::verify_git_branch(
             ws_meta.workspace_root.as_std_path(),
             &ws_config,
@@ -136,6 +175,13 @@ impl VersionStep {
             log::Level::Warn,
         )?;
 
+        for pkg in selected_pkgs.iter().filter(|p| p.versioned_by_tag()) {
+            let _ = crate::ops::shell::warn(format!(
+                "{} is versioned by tag, skipping it; run `cargo release tag` instead",
+                pkg.meta.name
+            ));
+        }
+
         // STEP 1: Release Confirmation
         super::confirm("Bump", &selected_pkgs, self.no_confirm, dry_run)?;
 
@@ -152,6 +198,124 @@ impl VersionStep {
         super::finish(failed, dry_run)
     }
 
+    /// Report inconsistent versions, exiting with the code of the first failed check
+    fn check(
+        &self,
+        ws_meta: &cargo_metadata::Metadata,
+        mut pkgs: indexmap::IndexMap<cargo_metadata::PackageId, plan::PackageRelease>,
+    ) -> Result<(), CliError> {
+        let (_selected_pkgs, excluded_pkgs) =
+            self.changed
+                .partition_packages(&self.workspace, ws_meta, &pkgs)?;
+        for excluded_pkg in excluded_pkgs {
+            pkgs.shift_remove(&excluded_pkg.id);
+        }
+        let pkgs = pkgs
+            .into_values()
+            .filter(|p| p.config.release())
+            .collect::<Vec<_>>();
+
+        let mut failures = Vec::new();
+
+        if super::find_shared_versions(&pkgs).is_err() {
+            failures.push(CHECK_SHARED_VERSION);
+        }
+
+        let mut dependents_ok = true;
+        for pkg in &pkgs {
+            let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
+            let manifests = std::iter::once(("workspace", workspace_path)).chain(
+                find_ws_members(ws_meta)
+                    .map(|d| (d.name.as_str(), d.manifest_path.as_std_path().to_owned())),
+            );
+            for (manifest_name, manifest_path) in manifests {
+                let problems = crate::ops::cargo::check_dependency_req(
+                    manifest_name,
+                    &manifest_path,
+                    &pkg.package_root,
+                    &pkg.meta.name,
+                    &pkg.initial_version.full_version,
+                    pkg.config.dependent_version(),
+                )?;
+                for problem in problems {
+                    dependents_ok = false;
+                    let _ = crate::ops::shell::error(problem);
+                }
+            }
+        }
+        if !dependents_ok {
+            failures.push(CHECK_DEPENDENT_REQ);
+        }
+
+        let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
+        let workspace: toml::Table = std::fs::read_to_string(&workspace_path)?
+            .parse()
+            .map_err(anyhow::Error::from)?;
+        let workspace_version = workspace
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str())
+            .map(semver::Version::parse)
+            .transpose()?;
+        let mut inheritors_ok = true;
+        for pkg in &pkgs {
+            if pkg.config.shared_version() != Some(crate::config::SharedVersion::WORKSPACE) {
+                continue;
+            }
+            match &workspace_version {
+                Some(workspace_version)
+                    if *workspace_version == pkg.initial_version.full_version => {}
+                Some(workspace_version) => {
+                    inheritors_ok = false;
+                    let _ = crate::ops::shell::error(format!(
+                        "{} has version {}, should be {} from `workspace.package.version`",
+                        pkg.meta.name, pkg.initial_version.full_version_string, workspace_version
+                    ));
+                }
+                None => {
+                    inheritors_ok = false;
+                    let _ = crate::ops::shell::error(format!(
+                        "{} shares the workspace version but `workspace.package.version` is unset",
+                        pkg.meta.name
+                    ));
+                }
+            }
+        }
+        if !inheritors_ok {
+            failures.push(CHECK_WORKSPACE_VERSION);
+        }
+
+        let mut tags_ok = true;
+        for pkg in &pkgs {
+            let Some(tag) = pkg.initial_tag() else {
+                continue;
+            };
+            if !git::tag_exists(ws_meta.workspace_root.as_std_path(), &tag)? {
+                continue;
+            }
+            let changed = changed_since(ws_meta, pkg, &tag).unwrap_or_default();
+            if !changed.is_empty() {
+                tags_ok = false;
+                let _ = crate::ops::shell::error(format!(
+                    "{} {} is already tagged as {tag} but has changed since: {changed:#?}",
+                    pkg.meta.name, pkg.initial_version.full_version_string
+                ));
+            }
+        }
+        if !tags_ok {
+            failures.push(CHECK_CHANGED_SINCE_TAG);
+        }
+
+        match failures.first() {
+            Some(code) => Err((*code).into()),
+            None => {
+                let _ = crate::ops::shell::status("Checked", "versions are consistent");
+                Ok(())
+            }
+        }
+    }
+
     fn to_config(&self) -> crate::config::ConfigArgs {
         crate::config::ConfigArgs {
             custom_config: self.custom_config.clone(),
@@ -163,6 +327,12 @@ impl VersionStep {
     }
 }
 
+// `version --check` exit codes, in the order the checks run
+const CHECK_SHARED_VERSION: i32 = 3;
+const CHECK_DEPENDENT_REQ: i32 = 4;
+const CHECK_WORKSPACE_VERSION: i32 = 5;
+const CHECK_CHANGED_SINCE_TAG: i32 = 6;
+
 pub fn changed_since(
     ws_meta: &cargo_metadata::Metadata,
     pkg: &plan::PackageRelease,
@@ -174,7 +344,21 @@ pub fn changed_since(
         // Limit our lookup since we don't need to check for `Cargo.lock`
         &pkg.package_root
     };
-    let changed = git::changed_files(changed_root, since_ref).ok().flatten()?;
+    let filter = super::changes::CommitFilter::new(&pkg.config).ok()?;
+    let changed = if pkg.config.commit_walk() == crate::config::CommitWalk::All && filter.is_empty()
+    {
+        git::changed_files(changed_root, since_ref).ok().flatten()?
+    } else {
+        let root = git::top_level(changed_root).ok()?;
+        let committed = super::changes::walk_commits(changed_root, pkg, since_ref)
+            .ok()?
+            .into_iter()
+            .flat_map(|c| c.paths)
+            .map(|p| root.join(p));
+        // Commits can't cover what is yet to be committed
+        let dirty = git::dirty_files(changed_root).ok()?;
+        committed.chain(dirty).unique().collect()
+    };
     let changed: Vec<_> = changed
         .into_iter()
         .filter(|p| pkg.package_content.contains(p))
@@ -219,6 +403,13 @@ pub fn update_versions(
         .map(|s| (true, s))
         .chain(excluded_pkgs.iter().map(|s| (false, s)))
     {
+        if pkg.versioned_by_tag() {
+            log::debug!(
+                "not writing the version of {}, it is versioned by tag",
+                pkg.meta.name
+            );
+            continue;
+        }
         let is_inherited =
             pkg.config.shared_version() == Some(crate::config::SharedVersion::WORKSPACE);
         let planned_version = if is_inherited {
@@ -308,7 +499,7 @@ pub fn update_dependent_versions(
     Ok(())
 }
 
-fn find_ws_members(
+pub fn find_ws_members(
     ws_meta: &cargo_metadata::Metadata,
 ) -> impl Iterator<Item = &cargo_metadata::Package> {
     let workspace_members: std::collections::HashSet<_> =
@@ -0,0 +1,40 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::commit_file;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let repo = git2::Repository::open(&project_root).unwrap();
+    cargo_test_support::git::tag(&repo, "cargo-list-test-fixture-v0.1.0");
+    cargo_test_support::git::tag(&repo, "cargo-list-test-fixture-dependency-v0.1.0");
+    commit_file(
+        &project_root,
+        "dependency/src/lib.rs",
+        "pub fn added() {}\n",
+        "feat: Add a function (#12)",
+    );
+    commit_file(
+        &project_root,
+        "primary/src/lib.rs",
+        "pub fn fixed() {}\n",
+        "fix!: Correct a function\n\nCo-authored-by: Other Person <other@example.com>",
+    );
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["changes", "--format", "json"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+}
@@ -0,0 +1,2 @@
+mod format_json;
+mod format_markdown;
@@ -2,6 +2,8 @@
 #![warn(clippy::needless_borrow)]
 #![warn(clippy::redundant_clone)]
 
+mod changes;
+mod release;
 mod version;
 
 fn init_registry() {
@@ -18,6 +20,20 @@ pub fn git_from(template: impl AsRef<std::path::Path>) -> cargo_test_support::Pr
     project
 }
 
+/// Write `contents` to `path` and commit it with `message`
+pub fn commit_file(project_root: &std::path::Path, path: &str, contents: &str, message: &str) {
+    std::fs::write(project_root.join(path), contents).unwrap();
+    let repo = git2::Repository::open(project_root).unwrap();
+    cargo_test_support::git::add(&repo);
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+        .unwrap();
+}
+
 fn create_default_gitconfig() {
     // If we're running this under a user account that has a different default branch set up
     // then tests that assume the default branch is master will fail. We set the default branch
@@ -0,0 +1,42 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let plan = snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args([
+            "patch",
+            "--package",
+            "cargo-list-test-fixture-unrelated",
+            "--no-publish",
+            "--plan-format",
+            "toml",
+        ])
+        .current_dir(cwd)
+        .assert()
+        .success();
+    std::fs::write(
+        project_root.join("release-plan.toml"),
+        &plan.get_output().stdout,
+    )
+    .unwrap();
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["apply", "release-plan.toml", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+}
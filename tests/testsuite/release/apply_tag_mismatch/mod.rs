@@ -0,0 +1,43 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let plan = snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["patch", "--workspace", "--plan-format", "toml"])
+        .current_dir(cwd)
+        .assert()
+        .success();
+    std::fs::write(
+        project_root.join("release-plan.toml"),
+        &plan.get_output().stdout,
+    )
+    .unwrap();
+    let repo = git2::Repository::open(&project_root).unwrap();
+    std::fs::write(
+        project_root.join("release.toml"),
+        "tag-name = \"release-{{version}}\"\n",
+    )
+    .unwrap();
+    cargo_test_support::git::add(&repo);
+    cargo_test_support::git::commit(&repo);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["apply", "release-plan.toml", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .code(101)
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+}
@@ -0,0 +1,54 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::compare::assert_ui;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::commit_file;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let repo = git2::Repository::open(&project_root).unwrap();
+    cargo_test_support::git::tag(&repo, "cargo-list-test-fixture-v0.1.0");
+    cargo_test_support::git::tag(&repo, "cargo-list-test-fixture-dependency-v0.1.0");
+    commit_file(
+        &project_root,
+        "dependency/src/lib.rs",
+        "pub fn added() {}\n",
+        "feat: Add a function",
+    );
+    commit_file(
+        &project_root,
+        "primary/src/lib.rs",
+        "pub fn fixed() {}\n",
+        "fix: Correct a function",
+    );
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["minor", "--workspace", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["dry_run_stdout.term.svg"])
+        .stderr_eq(file!["dry_run_stderr.term.svg"]);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["minor", "--workspace", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+
+    assert_ui().subset_matches(current_dir!().join("out"), &project_root);
+    let statuses = repo.statuses(None).unwrap();
+    assert!(statuses.iter().all(|s| s.status() == git2::Status::IGNORED));
+}
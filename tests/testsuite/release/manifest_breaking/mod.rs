@@ -0,0 +1,34 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::commit_file;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let repo = git2::Repository::open(&project_root).unwrap();
+    cargo_test_support::git::tag(&repo, "v1.0.0");
+    let manifest = std::fs::read_to_string(project_root.join("Cargo.toml")).unwrap();
+    commit_file(
+        &project_root,
+        "Cargo.toml",
+        &manifest.replace("1.70", "1.74"),
+        "chore: Raise MSRV",
+    );
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["minor", "--no-publish", "--no-push", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+}
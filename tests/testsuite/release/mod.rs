@@ -0,0 +1,12 @@
+mod apply;
+mod apply_manifest_changed;
+mod apply_overridden_flags;
+mod apply_tag_mismatch;
+mod changelog_consolidated;
+mod changelog_per_package;
+mod manifest_breaking;
+mod manifest_breaking_error;
+mod plan_format_json;
+mod resume;
+mod version_source_lightweight_tag;
+mod version_source_placeholder;
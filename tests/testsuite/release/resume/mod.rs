@@ -0,0 +1,52 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::compare::assert_ui;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    // The hook fails for the last package, after the others were committed
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["patch", "--workspace", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .code(101)
+        .stdout_eq(file!["failed_stdout.term.svg"])
+        .stderr_eq(file!["failed_stderr.term.svg"]);
+
+    let repo = git2::Repository::open(&project_root).unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    std::fs::write(project_root.join("hook-ok"), "").unwrap();
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["--resume", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+
+    assert_ui().subset_matches(current_dir!().join("out"), &project_root);
+    let tags = repo.tag_names(None).unwrap();
+    assert_eq!(
+        tags.iter().flatten().collect::<Vec<_>>(),
+        [
+            "cargo-list-test-fixture-dependency-v0.1.1",
+            "cargo-list-test-fixture-unrelated-v0.1.1",
+            "cargo-list-test-fixture-v0.1.1",
+        ]
+    );
+    assert!(!project_root.join("target/release-journal.toml").exists());
+}
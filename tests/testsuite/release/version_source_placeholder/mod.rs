@@ -0,0 +1,40 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["patch", "--no-publish", "--no-push", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .code(101)
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["1.0.0", "--no-publish", "--no-push", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["absolute_stdout.term.svg"])
+        .stderr_eq(file!["absolute_stderr.term.svg"]);
+
+    assert!(
+        git2::Repository::open(&project_root)
+            .unwrap()
+            .revparse_single("v1.0.0")
+            .is_ok()
+    );
+}
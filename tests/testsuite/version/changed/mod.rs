@@ -0,0 +1,42 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::compare::assert_ui;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let repo = git2::Repository::open(&project_root).unwrap();
+    for name in [
+        "cargo-list-test-fixture",
+        "cargo-list-test-fixture-dependency",
+        "cargo-list-test-fixture-unrelated",
+    ] {
+        cargo_test_support::git::tag(&repo, &format!("{name}-v0.1.0"));
+    }
+    std::fs::write(
+        project_root.join("dependency/src/lib.rs"),
+        "pub fn changed() {}\n",
+    )
+    .unwrap();
+    cargo_test_support::git::add(&repo);
+    cargo_test_support::git::commit(&repo);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["version", "patch", "--changed", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+
+    assert_ui().subset_matches(current_dir!().join("out"), &project_root);
+}
@@ -0,0 +1,59 @@
+use cargo_test_support::cargo_test;
+use cargo_test_support::current_dir;
+use cargo_test_support::file;
+
+use crate::CargoCommand;
+use crate::commit_file;
+use crate::git_from;
+use crate::init_registry;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    let repo = git2::Repository::open(&project_root).unwrap();
+    cargo_test_support::git::tag(&repo, "v0.1.0");
+    commit_file(
+        &project_root,
+        "README.md",
+        "# sample\n\nExcluded\n",
+        "docs: Expand README",
+    );
+    commit_file(
+        &project_root,
+        "src/lib.rs",
+        "pub fn deps() {}\n",
+        "chore(deps): Update dependencies",
+    );
+    std::fs::write(project_root.join("src/lib.rs"), "pub fn bot() {}\n").unwrap();
+    cargo_test_support::git::add(&repo);
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let bot =
+        git2::Signature::now("renovate[bot]", "renovate[bot]@users.noreply.github.com").unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &bot, &bot, "fix: Bump", &tree, &[&parent])
+        .unwrap();
+
+    // None of the commits count, so the package is unchanged
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["version", "--check"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["check_stdout.term.svg"])
+        .stderr_eq(file!["check_stderr.term.svg"]);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["version", "patch"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq(file!["stdout.term.svg"])
+        .stderr_eq(file!["stderr.term.svg"]);
+}
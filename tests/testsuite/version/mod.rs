@@ -1,6 +1,21 @@
+mod cascade_private;
+mod cascade_public;
+mod cascade_transitive;
+mod changed;
+mod changed_since;
+mod changed_with_dependents;
+mod changes_filtered;
+mod check_changed_since_tag;
+mod check_dependent_req;
+mod check_dirty_commit_walk;
+mod check_shared_version;
+mod check_workspace_version;
+mod dependent_version_error;
+mod dependent_version_fix_prerelease;
 mod downgrade_error;
 mod dry_run;
 mod ignore_dependent;
+mod metadata_auto;
 mod set_absolute_version;
 mod set_absolute_workspace_version;
 mod set_relative_version;